@@ -1,10 +1,21 @@
-use config::Config as _Config;
+use config::builder::DefaultState;
+use config::{Config as _Config, ConfigBuilder, Environment, File, FileFormat, Map, Source, Value};
 use cosm_tome::config::cfg::ChainConfig;
+use cosm_tome::signing_key::key::SigningKey;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use super::error::ConfigError;
+use super::chain_registry::{self, ChainConfigOverrides};
+use super::error::{ConfigError, KeyError};
+use super::keys::KeyConfig;
 use crate::orchestrator::deploy::DeployInfo;
+use crate::orchestrator::store::ArtifactFilter;
+
+/// Prefix of the environment variables that override config values.
+///
+/// Nested keys are separated by `__`, eg. `COSM_ORC_CHAIN_CFG__GRPC_ENDPOINT`
+/// overrides `chain_cfg.grpc_endpoint`.
+pub const ENV_PREFIX: &str = "COSM_ORC";
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Config {
@@ -12,15 +23,480 @@ pub struct Config {
     // used to configure already stored contract code_id and deployed addresses
     #[serde(default)]
     pub contract_deploy_info: HashMap<String, DeployInfo>,
+    // named signing keys that can be referenced by name in `CosmOrc` operations
+    #[serde(default)]
+    pub keys: HashMap<String, KeyConfig>,
+    // fallback endpoints, used in order when the `chain_cfg` endpoints are unreachable
+    #[serde(default)]
+    pub endpoints: Vec<Endpoint>,
+    // selects and names the wasm files uploaded by `CosmOrc::store_contracts()`
+    #[serde(default)]
+    pub artifacts: ArtifactFilter,
+}
+
+/// Chain api endpoint
+///
+/// ```yaml
+/// endpoints:
+///   - grpc: "https://grpc.example.com:443"
+///   - rpc: "https://rpc.example.com"
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Endpoint {
+    /// Cosmos gRPC api
+    Grpc(String),
+    /// Tendermint RPC api
+    Rpc(String),
+}
+
+impl Endpoint {
+    pub fn url(&self) -> &str {
+        match self {
+            Endpoint::Grpc(url) | Endpoint::Rpc(url) => url,
+        }
+    }
 }
 
 impl Config {
     /// Reads a yaml file containing a `ConfigInput` and converts it to a useable `Config` object.
+    ///
+    /// If the file contains a `networks:` section, the profile named by its `network:` key is used.
+    /// Environment variables, including `COSM_ORC_NETWORK`, are not read: use [ConfigLoader::env()] for that.
     pub fn from_yaml(file: &str) -> Result<Config, ConfigError> {
-        let settings = _Config::builder()
-            .add_source(config::File::with_name(file))
-            .build()?;
+        ConfigLoader::new().file(file).load()
+    }
+
+    /// Reads a yaml file with a `networks:` section and uses the `network` profile.
+    pub fn from_yaml_network(file: &str, network: &str) -> Result<Config, ConfigError> {
+        ConfigLoader::new().file(file).network(network).load()
+    }
+
+    /// Parses a yaml, json or toml `Config` from a string.
+    pub fn from_str(s: &str, format: FileFormat) -> Result<Config, ConfigError> {
+        ConfigLoader::new().str(s, format).load()
+    }
+
+    /// Merges `files` in order, so later files override values of earlier ones,
+    /// then applies `COSM_ORC_*` environment variable overrides.
+    ///
+    /// The format of each file is inferred from its extension.
+    pub fn from_files<S: AsRef<str>>(files: &[S]) -> Result<Config, ConfigError> {
+        files
+            .iter()
+            .fold(ConfigLoader::new(), |loader, f| loader.file(f.as_ref()))
+            .env()
+            .load()
+    }
+
+    /// Builds a `Config` from a cosmos chain registry `chain.json` file.
+    /// See [chain_registry::chain_config()] for how the values are picked.
+    pub fn from_chain_registry(
+        path: &str,
+        overrides: ChainConfigOverrides,
+    ) -> Result<Config, ConfigError> {
+        Ok(Config {
+            chain_cfg: chain_registry::chain_config(path, overrides)?,
+            contract_deploy_info: HashMap::new(),
+            keys: HashMap::new(),
+            endpoints: vec![],
+            artifacts: ArtifactFilter::default(),
+        })
+    }
+
+    /// Every configured endpoint in failover order: `chain_cfg.grpc_endpoint`,
+    /// `chain_cfg.rpc_endpoint`, then the fallback `endpoints`.
+    pub fn all_endpoints(&self) -> Vec<Endpoint> {
+        let primary = [
+            self.chain_cfg.grpc_endpoint.clone().map(Endpoint::Grpc),
+            self.chain_cfg.rpc_endpoint.clone().map(Endpoint::Rpc),
+        ];
+
+        primary
+            .into_iter()
+            .flatten()
+            .chain(self.endpoints.iter().cloned())
+            .collect()
+    }
+
+    /// Builds the `SigningKey` configured under `keys.<name>`.
+    pub fn signing_key(&self, name: &str) -> Result<SigningKey, KeyError> {
+        self.keys
+            .get(name)
+            .ok_or_else(|| KeyError::NotFound {
+                name: name.to_string(),
+            })?
+            .signing_key(name, &self.chain_cfg.derivation_path)
+    }
+}
+
+/// Builds a `Config` out of multiple layered sources.
+///
+/// Sources are merged in the order they are added, later sources override earlier ones.
+///
+/// If the merged config has a `networks:` section, the selected profile is merged on top of the
+/// top level values. The profile is selected by [Self::network()], or by the top level `network:` key
+/// which can be overridden with the `COSM_ORC_NETWORK` env var when [Self::env()] is used.
+///
+/// ```yaml
+/// network: local
+/// networks:
+///   local:
+///     chain_cfg:
+///       ...
+///     contract_deploy_info:
+///       ...
+///   testnet:
+///     chain_cfg:
+///       ...
+/// ```
+///
+/// ```no_run
+/// # use cosm_orc::config::cfg::ConfigLoader;
+/// # use cosm_orc::config::error::ConfigError;
+/// # fn main() -> Result<(), ConfigError> {
+/// let cfg = ConfigLoader::new()
+///     .file("./example-configs/juno.yaml")
+///     .optional_file("./example-configs/juno.ci.yaml")
+///     .env()
+///     .load()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct ConfigLoader {
+    builder: ConfigBuilder<DefaultState>,
+    env: Option<Environment>,
+    network: Option<String>,
+}
+
+impl ConfigLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a required config file. The format is inferred from the file extension.
+    pub fn file(mut self, path: &str) -> Self {
+        self.builder = self.builder.add_source(File::with_name(path));
+        self
+    }
+
+    /// Adds a config file that is skipped if it does not exist.
+    pub fn optional_file(mut self, path: &str) -> Self {
+        self.builder = self
+            .builder
+            .add_source(File::with_name(path).required(false));
+        self
+    }
+
+    /// Adds a config string in `format`.
+    pub fn str(mut self, s: &str, format: FileFormat) -> Self {
+        self.builder = self.builder.add_source(File::from_str(s, format));
+        self
+    }
+
+    /// Adds overrides from `COSM_ORC_*` environment variables.
+    ///
+    /// Env vars are applied last, on top of the selected network profile.
+    pub fn env(self) -> Self {
+        self.env_source(None)
+    }
+
+    fn env_source(mut self, vars: Option<Map<String, String>>) -> Self {
+        self.env = Some(
+            Environment::with_prefix(ENV_PREFIX)
+                .prefix_separator("_")
+                .separator("__")
+                .try_parsing(true)
+                .source(vars),
+        );
+        self
+    }
+
+    /// Selects the `networks.<name>` profile.
+    pub fn network<S: Into<String>>(mut self, name: S) -> Self {
+        self.network = Some(name.into());
+        self
+    }
+
+    pub fn load(self) -> Result<Config, ConfigError> {
+        let settings = self.build(None)?;
+
+        let network = self
+            .network
+            .clone()
+            .or_else(|| settings.get_string("network").ok());
+
+        let settings = match network {
+            Some(name) => {
+                let profile = settings
+                    .get_table(&format!("networks.{name}"))
+                    .map_err(|_| ConfigError::NetworkNotFound { name })?;
+
+                self.build(Some(NetworkProfile(profile)))?
+            }
+            None => {
+                if settings.get_table("networks").is_ok()
+                    && settings.get_table("chain_cfg").is_err()
+                {
+                    return Err(ConfigError::NetworkNotSelected);
+                }
+                settings
+            }
+        };
 
         Ok(settings.try_deserialize::<Config>()?)
     }
+
+    fn build(&self, profile: Option<NetworkProfile>) -> Result<_Config, ConfigError> {
+        let mut builder = self.builder.clone();
+
+        if let Some(profile) = profile {
+            builder = builder.add_source(profile);
+        }
+
+        if let Some(env) = &self.env {
+            builder = builder.add_source(env.clone());
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+/// Values of the selected `networks.<name>` profile
+#[derive(Clone, Debug)]
+struct NetworkProfile(Map<String, Value>);
+
+impl Source for NetworkProfile {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, config::ConfigError> {
+        Ok(self.0.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, ConfigLoader, Endpoint};
+    use crate::config::error::ConfigError;
+    use assert_matches::assert_matches;
+    use config::{FileFormat, Map};
+
+    const BASE: &str = r#"
+chain_cfg:
+  denom: "ujunox"
+  prefix: "juno"
+  chain_id: "testing"
+  grpc_endpoint: "http://localhost:9090/"
+  gas_price: 0.1
+  gas_adjustment: 1.5
+  derivation_path: "m/44'/118'/0'/0/0"
+
+contract_deploy_info:
+  cw20_base:
+    code_id: 1
+"#;
+
+    #[test]
+    fn from_str_formats() {
+        let cfg = Config::from_str(BASE, FileFormat::Yaml).unwrap();
+        assert_eq!(cfg.chain_cfg.chain_id, "testing");
+        assert_eq!(cfg.contract_deploy_info["cw20_base"].code_id, Some(1));
+
+        let json = serde_json::to_string(&cfg).unwrap();
+        let cfg = Config::from_str(&json, FileFormat::Json).unwrap();
+        assert_eq!(cfg.chain_cfg.chain_id, "testing");
+
+        let cfg = Config::from_str(
+            r#"
+            [chain_cfg]
+            denom = "ujunox"
+            prefix = "juno"
+            chain_id = "uni-5"
+            gas_price = 0.1
+            gas_adjustment = 1.5
+            derivation_path = "m/44'/118'/0'/0/0"
+            "#,
+            FileFormat::Toml,
+        )
+        .unwrap();
+        assert_eq!(cfg.chain_cfg.chain_id, "uni-5");
+        assert!(cfg.contract_deploy_info.is_empty());
+    }
+
+    #[test]
+    fn artifacts() {
+        let cfg = Config::from_str(
+            &format!(
+                "{BASE}
+artifacts:
+  include: [\"cw20_*\"]
+  contract_names:
+    cw20_base: token
+"
+            ),
+            FileFormat::Yaml,
+        )
+        .unwrap();
+
+        assert_eq!(cfg.artifacts.include, vec!["cw20_*"]);
+        assert!(cfg.artifacts.exclude.is_empty());
+        assert_eq!(cfg.artifacts.contract_names["cw20_base"], "token");
+    }
+
+    #[test]
+    fn fallback_endpoints() {
+        let cfg = Config::from_str(
+            &format!(
+                "{BASE}
+endpoints:
+  - rpc: \"http://localhost:26657/\"
+  - grpc: \"http://localhost:9091/\"
+"
+            ),
+            FileFormat::Yaml,
+        )
+        .unwrap();
+
+        assert_eq!(
+            cfg.all_endpoints(),
+            vec![
+                Endpoint::Grpc("http://localhost:9090/".to_string()),
+                Endpoint::Rpc("http://localhost:26657/".to_string()),
+                Endpoint::Grpc("http://localhost:9091/".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn layered_overrides() {
+        let env = Map::from([
+            (
+                "COSM_ORC_CHAIN_CFG__GRPC_ENDPOINT".to_string(),
+                "http://juno-testnet-grpc.polkachu.com:26090".to_string(),
+            ),
+            (
+                "COSM_ORC_CHAIN_CFG__GAS_PRICE".to_string(),
+                "0.2".to_string(),
+            ),
+            (
+                "OTHER_CHAIN_CFG__CHAIN_ID".to_string(),
+                "ignored".to_string(),
+            ),
+        ]);
+
+        let cfg = ConfigLoader::new()
+            .str(BASE, FileFormat::Yaml)
+            .str(
+                r#"
+chain_cfg:
+  chain_id: "uni-5"
+contract_deploy_info:
+  cw4_group:
+    code_id: 2
+"#,
+                FileFormat::Yaml,
+            )
+            .optional_file("./does-not-exist.yaml")
+            .env_source(Some(env))
+            .load()
+            .unwrap();
+
+        assert_eq!(cfg.chain_cfg.chain_id, "uni-5");
+        assert_eq!(cfg.chain_cfg.denom, "ujunox");
+        assert_eq!(
+            cfg.chain_cfg.grpc_endpoint.as_deref(),
+            Some("http://juno-testnet-grpc.polkachu.com:26090")
+        );
+        assert_eq!(cfg.chain_cfg.gas_price, 0.2);
+        assert_eq!(cfg.contract_deploy_info["cw20_base"].code_id, Some(1));
+        assert_eq!(cfg.contract_deploy_info["cw4_group"].code_id, Some(2));
+    }
+
+    const NETWORKS: &str = r#"
+network: local
+
+keys:
+  validator:
+    env: VALIDATOR_MNEMONIC
+
+networks:
+  local:
+    chain_cfg:
+      denom: "ujunox"
+      prefix: "juno"
+      chain_id: "testing"
+      grpc_endpoint: "http://localhost:9090/"
+      gas_price: 0.1
+      gas_adjustment: 1.5
+      derivation_path: "m/44'/118'/0'/0/0"
+    contract_deploy_info:
+      cw20_base:
+        code_id: 1
+  testnet:
+    chain_cfg:
+      denom: "ujunox"
+      prefix: "juno"
+      chain_id: "uni-5"
+      grpc_endpoint: "http://juno-testnet-grpc.polkachu.com:26090"
+      gas_price: 0.1
+      gas_adjustment: 1.5
+      derivation_path: "m/44'/118'/0'/0/0"
+"#;
+
+    #[test]
+    fn select_network() {
+        let cfg = ConfigLoader::new()
+            .str(NETWORKS, FileFormat::Yaml)
+            .load()
+            .unwrap();
+        assert_eq!(cfg.chain_cfg.chain_id, "testing");
+        assert_eq!(cfg.contract_deploy_info["cw20_base"].code_id, Some(1));
+        assert!(cfg.keys.contains_key("validator"));
+
+        let cfg = ConfigLoader::new()
+            .str(NETWORKS, FileFormat::Yaml)
+            .network("testnet")
+            .load()
+            .unwrap();
+        assert_eq!(cfg.chain_cfg.chain_id, "uni-5");
+        assert!(cfg.contract_deploy_info.is_empty());
+        assert!(cfg.keys.contains_key("validator"));
+
+        let env = Map::from([
+            ("COSM_ORC_NETWORK".to_string(), "testnet".to_string()),
+            (
+                "COSM_ORC_CHAIN_CFG__GRPC_ENDPOINT".to_string(),
+                "http://localhost:9091/".to_string(),
+            ),
+        ]);
+        let cfg = ConfigLoader::new()
+            .str(NETWORKS, FileFormat::Yaml)
+            .env_source(Some(env))
+            .load()
+            .unwrap();
+        assert_eq!(cfg.chain_cfg.chain_id, "uni-5");
+        assert_eq!(
+            cfg.chain_cfg.grpc_endpoint.as_deref(),
+            Some("http://localhost:9091/")
+        );
+    }
+
+    #[test]
+    fn missing_network() {
+        let err = ConfigLoader::new()
+            .str(NETWORKS, FileFormat::Yaml)
+            .network("mainnet")
+            .load()
+            .unwrap_err();
+        assert_matches!(err, ConfigError::NetworkNotFound { name } if name == "mainnet");
+
+        let err = ConfigLoader::new()
+            .str(&NETWORKS.replace("network: local", ""), FileFormat::Yaml)
+            .load()
+            .unwrap_err();
+        assert_matches!(err, ConfigError::NetworkNotSelected);
+    }
 }
@@ -0,0 +1,262 @@
+use cosm_tome::config::cfg::ChainConfig;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use super::error::ConfigError;
+
+/// Gas adjustment used when neither the overrides nor the chain registry provide one
+pub const DEFAULT_GAS_ADJUSTMENT: f64 = 1.3;
+
+/// Values that replace the ones read from a chain registry `chain.json` file.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ChainConfigOverrides {
+    pub denom: Option<String>,
+    pub prefix: Option<String>,
+    pub chain_id: Option<String>,
+    pub derivation_path: Option<String>,
+    pub rpc_endpoint: Option<String>,
+    pub grpc_endpoint: Option<String>,
+    pub gas_price: Option<f64>,
+    pub gas_adjustment: Option<f64>,
+}
+
+/// Subset of the cosmos chain registry `chain.json` schema
+#[derive(Clone, Debug, Deserialize)]
+struct ChainInfo {
+    chain_id: Option<String>,
+    bech32_prefix: Option<String>,
+    #[serde(default = "default_slip44")]
+    slip44: u32,
+    fees: Option<Fees>,
+    apis: Option<Apis>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Fees {
+    #[serde(default)]
+    fee_tokens: Vec<FeeToken>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct FeeToken {
+    denom: String,
+    fixed_min_gas_price: Option<f64>,
+    low_gas_price: Option<f64>,
+    average_gas_price: Option<f64>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+struct Apis {
+    #[serde(default)]
+    rpc: Vec<Endpoint>,
+    #[serde(default)]
+    grpc: Vec<Endpoint>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Endpoint {
+    address: String,
+}
+
+fn default_slip44() -> u32 {
+    118
+}
+
+/// Reads a chain registry `chain.json` file into a `ChainConfig`.
+///
+/// The fee token matching the `denom` override is used, or the first one if `denom` is not
+/// overridden, with its average gas price (falling back to the low / fixed minimum gas price). Endpoints can't be health checked offline, so the first well formed endpoint of
+/// each api is used.
+pub fn chain_config(
+    path: &str,
+    overrides: ChainConfigOverrides,
+) -> Result<ChainConfig, ConfigError> {
+    let json = fs::read_to_string(path).map_err(|e| ConfigError::ChainRegistryRead {
+        path: path.to_string(),
+        source: e,
+    })?;
+
+    parse_chain_config(&json, overrides)
+}
+
+fn parse_chain_config(
+    json: &str,
+    overrides: ChainConfigOverrides,
+) -> Result<ChainConfig, ConfigError> {
+    let info: ChainInfo =
+        serde_json::from_str(json).map_err(|e| ConfigError::ChainRegistryParse { source: e })?;
+
+    let fee_tokens = info.fees.map(|f| f.fee_tokens).unwrap_or_default();
+    let fee_token = match &overrides.denom {
+        Some(denom) => fee_tokens.into_iter().find(|t| &t.denom == denom),
+        None => fee_tokens.into_iter().next(),
+    };
+    let apis = info.apis.unwrap_or_default();
+
+    let gas_price = fee_token.as_ref().and_then(|t| {
+        t.average_gas_price
+            .or(t.low_gas_price)
+            .or(t.fixed_min_gas_price)
+    });
+
+    Ok(ChainConfig {
+        denom: required(overrides.denom.or(fee_token.map(|t| t.denom)), "denom")?,
+        prefix: required(overrides.prefix.or(info.bech32_prefix), "bech32_prefix")?,
+        chain_id: required(overrides.chain_id.or(info.chain_id), "chain_id")?,
+        derivation_path: overrides
+            .derivation_path
+            .unwrap_or_else(|| format!("m/44'/{}'/0'/0/0", info.slip44)),
+        rpc_endpoint: overrides.rpc_endpoint.or_else(|| first_endpoint(&apis.rpc)),
+        grpc_endpoint: overrides
+            .grpc_endpoint
+            .or_else(|| first_endpoint(&apis.grpc)),
+        gas_price: required(overrides.gas_price.or(gas_price), "gas_price")?,
+        gas_adjustment: overrides.gas_adjustment.unwrap_or(DEFAULT_GAS_ADJUSTMENT),
+    })
+}
+
+fn required<T>(value: Option<T>, field: &str) -> Result<T, ConfigError> {
+    value.ok_or_else(|| ConfigError::ChainRegistryMissingField {
+        field: field.to_string(),
+    })
+}
+
+/// Returns the first endpoint with a valid address, adding a url scheme if it is missing.
+fn first_endpoint(endpoints: &[Endpoint]) -> Option<String> {
+    endpoints.iter().find_map(|e| {
+        let address = e.address.trim();
+        if address.is_empty() || address.contains(char::is_whitespace) {
+            return None;
+        }
+
+        if address.starts_with("http://") || address.starts_with("https://") {
+            Some(address.to_string())
+        } else if address.ends_with(":443") {
+            Some(format!("https://{address}"))
+        } else {
+            Some(format!("http://{address}"))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_chain_config, ChainConfigOverrides};
+    use crate::config::error::ConfigError;
+    use assert_matches::assert_matches;
+
+    const CHAIN_JSON: &str = r#"{
+        "$schema": "../chain.schema.json",
+        "chain_name": "juno",
+        "status": "live",
+        "chain_id": "juno-1",
+        "bech32_prefix": "juno",
+        "slip44": 118,
+        "fees": {
+            "fee_tokens": [
+                {
+                    "denom": "ujuno",
+                    "fixed_min_gas_price": 0.0025,
+                    "low_gas_price": 0.03,
+                    "average_gas_price": 0.0625,
+                    "high_gas_price": 0.1
+                }
+            ]
+        },
+        "apis": {
+            "rpc": [
+                { "address": "", "provider": "broken" },
+                { "address": "https://rpc-juno.itastakers.com", "provider": "itastakers" }
+            ],
+            "grpc": [
+                { "address": "grpc-juno-ia.cosmosia.notional.ventures:443", "provider": "notional" }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn parse_chain_json() {
+        let cfg = parse_chain_config(CHAIN_JSON, ChainConfigOverrides::default()).unwrap();
+
+        assert_eq!(cfg.denom, "ujuno");
+        assert_eq!(cfg.prefix, "juno");
+        assert_eq!(cfg.chain_id, "juno-1");
+        assert_eq!(cfg.derivation_path, "m/44'/118'/0'/0/0");
+        assert_eq!(
+            cfg.rpc_endpoint.as_deref(),
+            Some("https://rpc-juno.itastakers.com")
+        );
+        assert_eq!(
+            cfg.grpc_endpoint.as_deref(),
+            Some("https://grpc-juno-ia.cosmosia.notional.ventures:443")
+        );
+        assert_eq!(cfg.gas_price, 0.0625);
+        assert_eq!(cfg.gas_adjustment, 1.3);
+    }
+
+    #[test]
+    fn apply_overrides() {
+        let cfg = parse_chain_config(
+            CHAIN_JSON,
+            ChainConfigOverrides {
+                chain_id: Some("testing".to_string()),
+                grpc_endpoint: Some("http://localhost:9090/".to_string()),
+                gas_adjustment: Some(1.5),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(cfg.chain_id, "testing");
+        assert_eq!(cfg.grpc_endpoint.as_deref(), Some("http://localhost:9090/"));
+        assert_eq!(cfg.gas_adjustment, 1.5);
+        assert_eq!(cfg.denom, "ujuno");
+    }
+
+    #[test]
+    fn override_fee_token() {
+        let json = r#"{
+            "chain_id": "osmosis-1",
+            "bech32_prefix": "osmo",
+            "fees": {
+                "fee_tokens": [
+                    { "denom": "uosmo", "average_gas_price": 0.025 },
+                    { "denom": "uion", "low_gas_price": 0.5 }
+                ]
+            }
+        }"#;
+
+        let cfg = parse_chain_config(
+            json,
+            ChainConfigOverrides {
+                denom: Some("uion".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(cfg.denom, "uion");
+        assert_eq!(cfg.gas_price, 0.5);
+
+        // a denom that is not a fee token needs its own gas price
+        let err = parse_chain_config(
+            json,
+            ChainConfigOverrides {
+                denom: Some("uatom".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert_matches!(err, ConfigError::ChainRegistryMissingField { field } if field == "gas_price");
+    }
+
+    #[test]
+    fn missing_fee_token() {
+        let err = parse_chain_config(
+            r#"{ "chain_id": "juno-1", "bech32_prefix": "juno" }"#,
+            ChainConfigOverrides::default(),
+        )
+        .unwrap_err();
+
+        assert_matches!(err, ConfigError::ChainRegistryMissingField { field } if field == "denom");
+    }
+}
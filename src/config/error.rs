@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use thiserror::Error;
 
 pub use config::ConfigError as BuilderError;
@@ -6,4 +7,71 @@ pub use config::ConfigError as BuilderError;
 pub enum ConfigError {
     #[error(transparent)]
     Config(#[from] BuilderError),
+
+    #[error("network {name} not found in config networks")]
+    NetworkNotFound { name: String },
+
+    #[error("config has multiple networks but none was selected")]
+    NetworkNotSelected,
+
+    #[error("error reading chain registry file {path}")]
+    ChainRegistryRead {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("error parsing chain registry json")]
+    ChainRegistryParse { source: serde_json::Error },
+
+    #[error("chain registry file is missing {field}")]
+    ChainRegistryMissingField { field: String },
+
+    #[error("chain_cfg.grpc_endpoint or chain_cfg.rpc_endpoint must be set")]
+    MissingEndpoint,
+
+    #[error("{key} must be an http(s) url, found {value:?}")]
+    InvalidEndpoint { key: String, value: String },
+
+    #[error("{key} must not be empty")]
+    EmptyValue { key: String },
+
+    #[error("{key} is not a valid derivation path (eg. \"m/44'/118'/0'/0/0\"), found {value:?}")]
+    InvalidDerivationPath { key: String, value: String },
+
+    #[error("{key} is not a valid glob pattern, found {value:?}")]
+    InvalidGlob { key: String, value: String },
+
+    #[error("chain_cfg.gas_price must be a non negative number, found {value}")]
+    InvalidGasPrice { value: f64 },
+
+    #[error("chain_cfg.gas_adjustment must be >= 1.0, found {value}")]
+    InvalidGasAdjustment { value: f64 },
+
+    #[error("{key} reuses code_id {code_id} already configured for contract_deploy_info.{other}")]
+    DuplicateCodeId {
+        key: String,
+        code_id: u64,
+        other: String,
+    },
+}
+
+#[derive(Error, Debug)]
+pub enum KeyError {
+    #[error("key {name} not found in config")]
+    NotFound { name: String },
+
+    #[error("no default key configured")]
+    NoDefaultKey,
+
+    #[error("error reading mnemonic from env var {var}")]
+    Env {
+        var: String,
+        source: std::env::VarError,
+    },
+
+    #[error("error reading mnemonic file {path:?}")]
+    File {
+        path: PathBuf,
+        source: std::io::Error,
+    },
 }
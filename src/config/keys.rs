@@ -0,0 +1,202 @@
+use cosm_tome::signing_key::key::{Key, SigningKey};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::error::KeyError;
+
+/// Signing key entry of the config `keys:` section.
+///
+/// ```yaml
+/// keys:
+///   validator:
+///     mnemonic: "word1 word2 ..."
+///   alice:
+///     env: ALICE_MNEMONIC
+///     derivation_path: "m/44'/118'/0'/0/1"
+///   bob:
+///     file: "./keys/bob.txt"
+///   carol:
+///     keyring:
+///       dir: "~/.cosm-orc/keys"
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KeyConfig {
+    /// Where the mnemonic is read from
+    #[serde(flatten)]
+    pub source: MnemonicSource,
+    /// Defaults to the `chain_cfg.derivation_path`
+    #[serde(default)]
+    pub derivation_path: Option<String>,
+}
+
+/// Location of a key mnemonic.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MnemonicSource {
+    /// Mnemonic stored in the config itself.
+    /// DO NOT USE FOR MAINNET
+    Mnemonic(String),
+    /// Name of the environment variable holding the mnemonic
+    Env(String),
+    /// Path of a file holding the mnemonic
+    File(PathBuf),
+    /// Directory holding one mnemonic file per key
+    Keyring(KeyringDir),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KeyringDir {
+    pub dir: PathBuf,
+    /// File name of the mnemonic inside of `dir`, defaults to the key name
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl KeyConfig {
+    /// Reads the mnemonic and builds the `SigningKey` named `name`.
+    pub fn signing_key(&self, name: &str, default_path: &str) -> Result<SigningKey, KeyError> {
+        let mnemonic = match &self.source {
+            MnemonicSource::Mnemonic(phrase) => phrase.clone(),
+            MnemonicSource::Env(var) => env::var(var).map_err(|e| KeyError::Env {
+                var: var.clone(),
+                source: e,
+            })?,
+            MnemonicSource::File(path) => read_mnemonic(path)?,
+            MnemonicSource::Keyring(keyring) => {
+                let file = keyring.name.as_deref().unwrap_or(name);
+                read_mnemonic(&expand_home(&keyring.dir).join(file))?
+            }
+        };
+
+        Ok(SigningKey {
+            name: name.to_string(),
+            key: Key::Mnemonic(mnemonic.trim().to_string()),
+            derivation_path: self
+                .derivation_path
+                .clone()
+                .unwrap_or_else(|| default_path.to_string()),
+        })
+    }
+}
+
+fn read_mnemonic(path: &Path) -> Result<String, KeyError> {
+    fs::read_to_string(path).map_err(|e| KeyError::File {
+        path: path.to_path_buf(),
+        source: e,
+    })
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyConfig, KeyringDir, MnemonicSource};
+    use crate::config::error::KeyError;
+    use assert_matches::assert_matches;
+    use cosm_tome::signing_key::key::Key;
+    use std::collections::HashMap;
+    use std::fs;
+
+    const DEFAULT_PATH: &str = "m/44'/118'/0'/0/0";
+
+    #[test]
+    fn deserialize_key_sources() {
+        let keys: HashMap<String, KeyConfig> = parse_keys(
+            r#"
+            validator:
+              mnemonic: "word1 word2"
+            alice:
+              env: ALICE_MNEMONIC
+              derivation_path: "m/44'/118'/0'/0/1"
+            bob:
+              file: "./keys/bob.txt"
+            carol:
+              keyring:
+                dir: "/keys"
+            "#,
+        );
+
+        assert_eq!(
+            keys["validator"].source,
+            MnemonicSource::Mnemonic("word1 word2".to_string())
+        );
+        assert_eq!(
+            keys["alice"].source,
+            MnemonicSource::Env("ALICE_MNEMONIC".to_string())
+        );
+        assert_eq!(
+            keys["alice"].derivation_path.as_deref(),
+            Some("m/44'/118'/0'/0/1")
+        );
+        assert_eq!(
+            keys["bob"].source,
+            MnemonicSource::File("./keys/bob.txt".into())
+        );
+        assert_eq!(
+            keys["carol"].source,
+            MnemonicSource::Keyring(KeyringDir {
+                dir: "/keys".into(),
+                name: None,
+            })
+        );
+    }
+
+    #[test]
+    fn reads_mnemonic_sources() {
+        let dir = std::env::temp_dir().join("cosm_orc_keyring_test");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("carol"), "word5 word6\n").unwrap();
+
+        let key = KeyConfig {
+            source: MnemonicSource::Keyring(KeyringDir {
+                dir: dir.clone(),
+                name: None,
+            }),
+            derivation_path: None,
+        }
+        .signing_key("carol", DEFAULT_PATH)
+        .unwrap();
+
+        assert_eq!(key.name, "carol");
+        assert_eq!(key.key, Key::Mnemonic("word5 word6".to_string()));
+        assert_eq!(key.derivation_path, DEFAULT_PATH);
+
+        std::env::set_var("COSM_ORC_TEST_MNEMONIC", "word3 word4");
+        let key = KeyConfig {
+            source: MnemonicSource::Env("COSM_ORC_TEST_MNEMONIC".to_string()),
+            derivation_path: Some("m/44'/118'/0'/0/1".to_string()),
+        }
+        .signing_key("alice", DEFAULT_PATH)
+        .unwrap();
+
+        assert_eq!(key.key, Key::Mnemonic("word3 word4".to_string()));
+        assert_eq!(key.derivation_path, "m/44'/118'/0'/0/1");
+
+        let err = KeyConfig {
+            source: MnemonicSource::File(dir.join("missing")),
+            derivation_path: None,
+        }
+        .signing_key("bob", DEFAULT_PATH)
+        .unwrap_err();
+
+        assert_matches!(err, KeyError::File { .. });
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    fn parse_keys(yaml: &str) -> HashMap<String, KeyConfig> {
+        config::Config::builder()
+            .add_source(config::File::from_str(yaml, config::FileFormat::Yaml))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap()
+    }
+}
@@ -1,6 +1,12 @@
 pub mod cfg;
 
+pub mod chain_registry;
+
 pub mod error;
 
+pub mod keys;
+
+mod validate;
+
 pub use config::ConfigError;
 pub use cosm_tome::config::cfg::ChainConfig;
@@ -0,0 +1,297 @@
+use globset::Glob;
+use std::collections::BTreeMap;
+
+use super::cfg::Config;
+use super::error::ConfigError;
+
+impl Config {
+    /// Checks the config for mistakes that would otherwise only surface as chain errors.
+    ///
+    /// Returns the first problem found, naming the offending config key.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        self.validate_with(true)
+    }
+
+    /// Same as [Self::validate()], optionally allowing configs without any endpoint
+    pub(crate) fn validate_with(&self, require_endpoint: bool) -> Result<(), ConfigError> {
+        let chain_cfg = &self.chain_cfg;
+
+        for (key, value) in [
+            ("chain_cfg.denom", &chain_cfg.denom),
+            ("chain_cfg.prefix", &chain_cfg.prefix),
+            ("chain_cfg.chain_id", &chain_cfg.chain_id),
+        ] {
+            if value.trim().is_empty() {
+                return Err(ConfigError::EmptyValue {
+                    key: key.to_string(),
+                });
+            }
+        }
+
+        if require_endpoint && self.all_endpoints().is_empty() {
+            return Err(ConfigError::MissingEndpoint);
+        }
+
+        let endpoints = [
+            (
+                "chain_cfg.grpc_endpoint".to_string(),
+                &chain_cfg.grpc_endpoint,
+            ),
+            (
+                "chain_cfg.rpc_endpoint".to_string(),
+                &chain_cfg.rpc_endpoint,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.as_deref().map(|v| (key, v)))
+        .chain(
+            self.endpoints
+                .iter()
+                .enumerate()
+                .map(|(i, e)| (format!("endpoints[{i}]"), e.url())),
+        );
+
+        for (key, value) in endpoints {
+            if !is_http_url(value) {
+                return Err(ConfigError::InvalidEndpoint {
+                    key,
+                    value: value.to_string(),
+                });
+            }
+        }
+
+        validate_derivation_path("chain_cfg.derivation_path", &chain_cfg.derivation_path)?;
+
+        if !chain_cfg.gas_price.is_finite() || chain_cfg.gas_price < 0.0 {
+            return Err(ConfigError::InvalidGasPrice {
+                value: chain_cfg.gas_price,
+            });
+        }
+
+        if !chain_cfg.gas_adjustment.is_finite() || chain_cfg.gas_adjustment < 1.0 {
+            return Err(ConfigError::InvalidGasAdjustment {
+                value: chain_cfg.gas_adjustment,
+            });
+        }
+
+        // sorted so the reported duplicate is deterministic
+        let mut code_ids: BTreeMap<u64, &str> = BTreeMap::new();
+        let deploy_info = self.contract_deploy_info.iter().collect::<BTreeMap<_, _>>();
+        for (name, info) in deploy_info {
+            if let Some(code_id) = info.code_id {
+                if let Some(other) = code_ids.insert(code_id, name) {
+                    return Err(ConfigError::DuplicateCodeId {
+                        key: format!("contract_deploy_info.{name}.code_id"),
+                        code_id,
+                        other: other.to_string(),
+                    });
+                }
+            }
+        }
+
+        let keys = self.keys.iter().collect::<BTreeMap<_, _>>();
+        for (name, key) in keys {
+            if let Some(path) = &key.derivation_path {
+                validate_derivation_path(&format!("keys.{name}.derivation_path"), path)?;
+            }
+        }
+
+        let globs = [
+            ("artifacts.include", &self.artifacts.include),
+            ("artifacts.exclude", &self.artifacts.exclude),
+        ];
+        for (key, patterns) in globs {
+            for (i, pattern) in patterns.iter().enumerate() {
+                if Glob::new(pattern).is_err() {
+                    return Err(ConfigError::InvalidGlob {
+                        key: format!("{key}[{i}]"),
+                        value: pattern.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_http_url(value: &str) -> bool {
+    let host = value
+        .strip_prefix("http://")
+        .or_else(|| value.strip_prefix("https://"));
+
+    matches!(host, Some(h) if !h.is_empty() && !h.starts_with('/') && !h.contains(char::is_whitespace))
+}
+
+/// Accepts bip32 paths like `m/44'/118'/0'/0/0`
+fn validate_derivation_path(key: &str, path: &str) -> Result<(), ConfigError> {
+    let mut parts = path.split('/');
+
+    let valid = parts.next() == Some("m")
+        && path.len() > 1
+        && parts.all(|p| {
+            let index = p.strip_suffix('\'').unwrap_or(p);
+            !index.is_empty() && index.parse::<u32>().is_ok_and(|i| i < 1 << 31)
+        });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ConfigError::InvalidDerivationPath {
+            key: key.to_string(),
+            value: path.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::cfg::{Config, Endpoint};
+    use crate::config::error::ConfigError;
+    use crate::config::keys::{KeyConfig, MnemonicSource};
+    use crate::orchestrator::deploy::DeployInfo;
+    use crate::orchestrator::store::ArtifactFilter;
+    use assert_matches::assert_matches;
+    use cosm_tome::config::cfg::ChainConfig;
+    use std::collections::HashMap;
+
+    fn valid_cfg() -> Config {
+        Config {
+            chain_cfg: ChainConfig {
+                denom: "ujunox".to_string(),
+                prefix: "juno".to_string(),
+                chain_id: "testing".to_string(),
+                derivation_path: "m/44'/118'/0'/0/0".to_string(),
+                rpc_endpoint: None,
+                grpc_endpoint: Some("http://localhost:9090/".to_string()),
+                gas_price: 0.1,
+                gas_adjustment: 1.5,
+            },
+            contract_deploy_info: HashMap::from([
+                (
+                    "cw20_base".to_string(),
+                    DeployInfo {
+                        code_id: Some(1),
+                        address: None,
+                    },
+                ),
+                (
+                    "cw4_group".to_string(),
+                    DeployInfo {
+                        code_id: Some(2),
+                        address: None,
+                    },
+                ),
+            ]),
+            keys: HashMap::new(),
+            endpoints: vec![],
+            artifacts: ArtifactFilter::default(),
+        }
+    }
+
+    #[test]
+    fn valid_config() {
+        valid_cfg().validate().unwrap();
+    }
+
+    #[test]
+    fn invalid_chain_cfg() {
+        let mut cfg = valid_cfg();
+        cfg.chain_cfg.grpc_endpoint = None;
+        assert_matches!(cfg.validate().unwrap_err(), ConfigError::MissingEndpoint);
+
+        let mut cfg = valid_cfg();
+        cfg.chain_cfg.rpc_endpoint = Some("localhost:26657".to_string());
+        assert_matches!(
+            cfg.validate().unwrap_err(),
+            ConfigError::InvalidEndpoint { key, .. } if key == "chain_cfg.rpc_endpoint"
+        );
+
+        let mut cfg = valid_cfg();
+        cfg.chain_cfg.chain_id = "".to_string();
+        assert_matches!(
+            cfg.validate().unwrap_err(),
+            ConfigError::EmptyValue { key } if key == "chain_cfg.chain_id"
+        );
+
+        let mut cfg = valid_cfg();
+        cfg.chain_cfg.derivation_path = "m/44'/118'/0'/0/x".to_string();
+        assert_matches!(
+            cfg.validate().unwrap_err(),
+            ConfigError::InvalidDerivationPath { key, .. } if key == "chain_cfg.derivation_path"
+        );
+
+        let mut cfg = valid_cfg();
+        cfg.chain_cfg.gas_adjustment = 0.9;
+        assert_matches!(
+            cfg.validate().unwrap_err(),
+            ConfigError::InvalidGasAdjustment { .. }
+        );
+
+        let mut cfg = valid_cfg();
+        cfg.chain_cfg.gas_price = -0.1;
+        assert_matches!(
+            cfg.validate().unwrap_err(),
+            ConfigError::InvalidGasPrice { .. }
+        );
+    }
+
+    #[test]
+    fn fallback_endpoints() {
+        let mut cfg = valid_cfg();
+        cfg.chain_cfg.grpc_endpoint = None;
+        cfg.endpoints = vec![Endpoint::Rpc("http://localhost:26657/".to_string())];
+        cfg.validate().unwrap();
+
+        cfg.endpoints
+            .push(Endpoint::Grpc("localhost:9090".to_string()));
+        assert_matches!(
+            cfg.validate().unwrap_err(),
+            ConfigError::InvalidEndpoint { key, .. } if key == "endpoints[1]"
+        );
+    }
+
+    #[test]
+    fn invalid_artifact_glob() {
+        let mut cfg = valid_cfg();
+        cfg.artifacts.include = vec!["cw20_*".to_string()];
+        cfg.artifacts.exclude = vec!["*_test*".to_string(), "cw4_[".to_string()];
+
+        assert_matches!(
+            cfg.validate().unwrap_err(),
+            ConfigError::InvalidGlob { key, .. } if key == "artifacts.exclude[1]"
+        );
+    }
+
+    #[test]
+    fn duplicate_code_ids() {
+        let mut cfg = valid_cfg();
+        cfg.contract_deploy_info
+            .get_mut("cw4_group")
+            .unwrap()
+            .code_id = Some(1);
+
+        assert_matches!(
+            cfg.validate().unwrap_err(),
+            ConfigError::DuplicateCodeId { key, code_id: 1, other }
+                if key == "contract_deploy_info.cw4_group.code_id" && other == "cw20_base"
+        );
+    }
+
+    #[test]
+    fn invalid_key_derivation_path() {
+        let mut cfg = valid_cfg();
+        cfg.keys.insert(
+            "validator".to_string(),
+            KeyConfig {
+                source: MnemonicSource::Env("VALIDATOR_MNEMONIC".to_string()),
+                derivation_path: Some("44'/118'/0'/0/0".to_string()),
+            },
+        );
+
+        assert_matches!(
+            cfg.validate().unwrap_err(),
+            ConfigError::InvalidDerivationPath { key, .. } if key == "keys.validator.derivation_path"
+        );
+    }
+}
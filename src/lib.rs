@@ -159,6 +159,8 @@ pub mod orchestrator;
 
 pub mod config;
 
+pub mod testing;
+
 // re-export all of cosm-tome in case people want to use arbitrary
 // cosmtome types when accessing the underlying `cosm_orc.client`
 pub use cosm_tome;
@@ -0,0 +1,293 @@
+use std::ops::Deref;
+use tracing::{info, warn};
+
+use cosm_tome::chain::coin::{Coin, Denom};
+use cosm_tome::chain::request::TxOptions;
+use cosm_tome::clients::client::{CosmTome, CosmosClient};
+use cosm_tome::config::cfg::ChainConfig;
+use cosm_tome::modules::auth::model::Address;
+use cosm_tome::modules::bank::error::BankError;
+use cosm_tome::modules::bank::model::SendRequest;
+use cosm_tome::signing_key::key::{Key, SigningKey};
+
+use super::cosm_orc::tokio_block;
+use super::error::ProcessError;
+use super::gas_profiler::fee_paid;
+
+/// Gas limit reserved for the refund tx of each account
+const REFUND_GAS_LIMIT: u64 = 200_000;
+
+/// Creates funded test accounts with [CosmOrc::create_accounts()](crate::orchestrator::cosm_orc::CosmOrc::create_accounts).
+#[derive(Clone, Debug)]
+pub struct AccountFactory {
+    /// Key that pays for the funding tx and receives refunds
+    pub funder: SigningKey,
+    /// Tokens sent to every created account
+    pub funds: Vec<Coin>,
+    /// Derives accounts from this mnemonic instead of generating random ones.
+    /// The n-th account uses the chain derivation path with its last index replaced by `n`.
+    pub mnemonic: Option<String>,
+    /// Key name prefix, accounts are named `{name_prefix}_{n}`
+    pub name_prefix: String,
+    /// Send the remaining `funds` denoms back to `funder` when the accounts are dropped
+    pub refund: bool,
+}
+
+impl AccountFactory {
+    pub fn new(funder: SigningKey, funds: Vec<Coin>) -> Self {
+        Self {
+            funder,
+            funds,
+            mnemonic: None,
+            name_prefix: "test_account".to_string(),
+            refund: false,
+        }
+    }
+
+    /// Derives deterministic accounts from `mnemonic`
+    pub fn mnemonic<S: Into<String>>(mut self, mnemonic: S) -> Self {
+        self.mnemonic = Some(mnemonic.into());
+        self
+    }
+
+    /// Names the created keys `{name_prefix}_{n}`
+    pub fn name_prefix<S: Into<String>>(mut self, name_prefix: S) -> Self {
+        self.name_prefix = name_prefix.into();
+        self
+    }
+
+    /// Returns leftover funds to the funder on drop / [TestAccounts::teardown()]
+    pub fn refund(mut self, refund: bool) -> Self {
+        self.refund = refund;
+        self
+    }
+
+    pub(crate) fn key(&self, n: usize, derivation_path: &str) -> SigningKey {
+        let name = format!("{}_{}", self.name_prefix, n);
+
+        match &self.mnemonic {
+            Some(mnemonic) => SigningKey {
+                name,
+                key: Key::Mnemonic(mnemonic.clone()),
+                derivation_path: indexed_path(derivation_path, n),
+            },
+            None => SigningKey::random_mnemonic(name, derivation_path.to_string()),
+        }
+    }
+}
+
+/// Replaces the address index of a derivation path, eg. `m/44'/118'/0'/0/0` -> `m/44'/118'/0'/0/{n}`
+fn indexed_path(derivation_path: &str, n: usize) -> String {
+    match derivation_path.rsplit_once('/') {
+        Some((head, _)) => format!("{head}/{n}"),
+        None => format!("{derivation_path}/{n}"),
+    }
+}
+
+/// Funded account created by an [AccountFactory]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestAccount {
+    pub key: SigningKey,
+    pub address: Address,
+}
+
+/// Accounts created by [CosmOrc::create_accounts()](crate::orchestrator::cosm_orc::CosmOrc::create_accounts).
+///
+/// If the factory was configured with `refund`, leftover funds are sent back to the funder
+/// when this is dropped. Errors are only logged on drop, use [Self::teardown()] to handle them.
+/// Dropping must happen outside of an async runtime.
+pub struct TestAccounts<C: CosmosClient> {
+    accounts: Vec<TestAccount>,
+    refund: Option<Refund<C>>,
+}
+
+impl<C: CosmosClient> TestAccounts<C> {
+    pub(crate) fn new(accounts: Vec<TestAccount>, refund: Option<Refund<C>>) -> Self {
+        Self { accounts, refund }
+    }
+
+    /// Returns leftover funds to the funder, if enabled.
+    pub fn teardown(mut self) -> Result<(), ProcessError> {
+        match self.refund.take() {
+            Some(refund) => refund.run(&self.accounts),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the accounts without refunding them on drop.
+    pub fn into_inner(mut self) -> Vec<TestAccount> {
+        self.refund = None;
+        std::mem::take(&mut self.accounts)
+    }
+}
+
+impl<C: CosmosClient> Deref for TestAccounts<C> {
+    type Target = [TestAccount];
+
+    fn deref(&self) -> &Self::Target {
+        &self.accounts
+    }
+}
+
+impl<C: CosmosClient> Drop for TestAccounts<C> {
+    fn drop(&mut self) {
+        if let Some(refund) = self.refund.take() {
+            if let Err(e) = refund.run(&self.accounts) {
+                warn!("failed to refund test accounts: {e}");
+            }
+        }
+    }
+}
+
+pub(crate) struct Refund<C: CosmosClient> {
+    pub(crate) client: CosmTome<C>,
+    pub(crate) plan: RefundPlan,
+}
+
+impl<C: CosmosClient> Refund<C> {
+    fn run(&self, accounts: &[TestAccount]) -> Result<(), ProcessError> {
+        self.plan.run(&self.client, accounts)
+    }
+}
+
+/// Sends the leftover `denoms` balances of test accounts back to the funder
+pub(crate) struct RefundPlan {
+    pub(crate) tx_options: TxOptions,
+    pub(crate) funder: Address,
+    pub(crate) denoms: Vec<Denom>,
+    /// Kept by each account to pay for its refund tx
+    pub(crate) fee_reserve: Vec<Coin>,
+}
+
+impl RefundPlan {
+    pub(crate) fn new(
+        cfg: &ChainConfig,
+        tx_options: TxOptions,
+        funder: Address,
+        funds: &[Coin],
+    ) -> Self {
+        let mut denoms: Vec<Denom> = vec![];
+        for coin in funds {
+            if !denoms.contains(&coin.denom) {
+                denoms.push(coin.denom.clone());
+            }
+        }
+
+        // same gas limit the simulated fee of the refund tx is computed with
+        let gas_limit = (REFUND_GAS_LIMIT as f64 * cfg.gas_adjustment).ceil() as u64;
+        let fee_reserve = fee_paid(cfg, &tx_options, gas_limit);
+
+        Self {
+            tx_options,
+            funder,
+            denoms,
+            fee_reserve,
+        }
+    }
+
+    /// Refunds every account, even if some of them fail
+    pub(crate) fn run<C: CosmosClient>(
+        &self,
+        client: &CosmTome<C>,
+        accounts: &[TestAccount],
+    ) -> Result<(), ProcessError> {
+        let mut errors = vec![];
+
+        tokio_block(async {
+            for account in accounts {
+                if let Err(e) = self.refund(client, account).await {
+                    warn!("failed to refund {}: {e}", account.address);
+                    errors.push((account.address.to_string(), e));
+                }
+            }
+        });
+
+        if !errors.is_empty() {
+            return Err(ProcessError::RefundFailed { errors });
+        }
+
+        Ok(())
+    }
+
+    async fn refund<C: CosmosClient>(
+        &self,
+        client: &CosmTome<C>,
+        account: &TestAccount,
+    ) -> Result<(), BankError> {
+        let mut amounts = vec![];
+
+        for denom in &self.denoms {
+            let balance = client
+                .bank_query_balance(account.address.clone(), denom.clone())
+                .await?
+                .balance;
+
+            let reserve = self
+                .fee_reserve
+                .iter()
+                .find(|c| c.denom == balance.denom)
+                .map_or(0, |c| c.amount);
+
+            if balance.amount > reserve {
+                amounts.push(Coin {
+                    denom: balance.denom,
+                    amount: balance.amount - reserve,
+                });
+            }
+        }
+
+        if amounts.is_empty() {
+            return Ok(());
+        }
+
+        let req = SendRequest {
+            from: account.address.clone(),
+            to: self.funder.clone(),
+            amounts,
+        };
+
+        let res = client
+            .bank_send(req, &account.key, &self.tx_options)
+            .await?;
+
+        info!(
+            "refunded {} to {}: {}",
+            account.address, self.funder, res.res.tx_hash
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{indexed_path, AccountFactory};
+    use cosm_tome::signing_key::key::{Key, SigningKey};
+
+    #[test]
+    fn derives_deterministic_keys() {
+        let funder = SigningKey::random_mnemonic("funder".to_string(), "m".to_string());
+        let factory = AccountFactory::new(funder, vec![])
+            .mnemonic("word1 word2")
+            .name_prefix("user");
+
+        let key = factory.key(3, "m/44'/118'/0'/0/0");
+        assert_eq!(key.name, "user_3");
+        assert_eq!(key.key, Key::Mnemonic("word1 word2".to_string()));
+        assert_eq!(key.derivation_path, "m/44'/118'/0'/0/3");
+
+        assert_eq!(indexed_path("m/44'/330'/0'/0/0", 12), "m/44'/330'/0'/0/12");
+    }
+
+    #[test]
+    fn generates_random_keys() {
+        let funder = SigningKey::random_mnemonic("funder".to_string(), "m".to_string());
+        let factory = AccountFactory::new(funder, vec![]);
+
+        let a = factory.key(0, "m/44'/118'/0'/0/0");
+        let b = factory.key(1, "m/44'/118'/0'/0/0");
+        assert_eq!(a.name, "test_account_0");
+        assert_eq!(a.derivation_path, "m/44'/118'/0'/0/0");
+        assert_ne!(a.key, b.key);
+    }
+}
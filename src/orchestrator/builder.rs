@@ -0,0 +1,364 @@
+use std::path::PathBuf;
+
+use cosm_tome::chain::request::TxOptions;
+use cosm_tome::clients::client::CosmosClient;
+use cosm_tome::signing_key::key::SigningKey;
+
+use super::client::ChainClient;
+use super::cosm_orc::CosmOrc;
+use super::error::InitError;
+use super::poll::PollPolicy;
+use super::retry::RetryPolicy;
+use super::sequence::SequenceManager;
+use super::store::{ArtifactFilter, StorePolicy};
+use crate::config::cfg::{Config, Endpoint};
+
+/// Cosmos api used to talk to the chain
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// Every configured endpoint, in the order of [Config::all_endpoints()]
+    #[default]
+    Auto,
+    /// Only the Cosmos gRPC endpoints
+    CosmosgRPC,
+    /// Only the tendermint RPC endpoints
+    TendermintRPC,
+}
+
+#[derive(Clone, Debug)]
+enum DefaultKey {
+    Key(SigningKey),
+    Name(String),
+}
+
+/// Configures and builds a [CosmOrc].
+///
+/// ```no_run
+/// # use std::error::Error;
+/// # use cosm_orc::config::cfg::Config;
+/// # use cosm_orc::orchestrator::cosm_orc::CosmOrc;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let cosm_orc = CosmOrc::builder()
+///     .config(Config::from_yaml("./example-configs/juno_local.yaml")?)
+///     .gas_profiler(true)
+///     .default_key_name("validator")
+///     .label("{contract_name}-test")
+///     .state_file("./cosm-orc-state.json")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CosmOrcBuilder {
+    cfg: Option<Config>,
+    backend: Backend,
+    gas_profiler: bool,
+    tx_options: Option<TxOptions>,
+    default_key: Option<DefaultKey>,
+    label: Option<String>,
+    poll_policy: Option<PollPolicy>,
+    retry_policy: Option<RetryPolicy>,
+    store_policy: Option<StorePolicy>,
+    state_file: Option<PathBuf>,
+    transcript: Option<PathBuf>,
+    sequences: Option<SequenceManager>,
+}
+
+impl CosmOrcBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the config. Required.
+    pub fn config(mut self, cfg: Config) -> Self {
+        self.cfg = Some(cfg);
+        self
+    }
+
+    /// Selects the backing api used by [Self::build()]. Defaults to [Backend::Auto].
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Enables the gas profiler. Disabled by default.
+    pub fn gas_profiler(mut self, enabled: bool) -> Self {
+        self.gas_profiler = enabled;
+        self
+    }
+
+    /// Sets the `TxOptions` used for every tx
+    pub fn tx_options(mut self, tx_options: TxOptions) -> Self {
+        self.tx_options = Some(tx_options);
+        self
+    }
+
+    /// Sets the key used by operations that are not given an explicit key
+    pub fn default_key(mut self, key: SigningKey) -> Self {
+        self.default_key = Some(DefaultKey::Key(key));
+        self
+    }
+
+    /// Same as [Self::default_key()], using the key named `name` in `Config::keys`
+    pub fn default_key_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.default_key = Some(DefaultKey::Name(name.into()));
+        self
+    }
+
+    /// Sets the label of instantiated contracts.
+    /// `{contract_name}` and `{op_name}` are replaced by the instantiate arguments.
+    pub fn label<S: Into<String>>(mut self, label: S) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Sets the policy used while polling for new blocks
+    pub fn poll_policy(mut self, poll_policy: PollPolicy) -> Self {
+        self.poll_policy = Some(poll_policy);
+        self
+    }
+
+    /// Sets the policy used to retry operations after transient failures
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Sets the policy used to check wasm files before they are stored.
+    ///
+    /// The `artifacts:` section of the config is kept unless `store_policy.artifacts` is set.
+    pub fn store_policy(mut self, store_policy: StorePolicy) -> Self {
+        self.store_policy = Some(store_policy);
+        self
+    }
+
+    /// Persists stored code ids and contract addresses to `path`.
+    ///
+    /// Entries already saved in `path` override the `contract_deploy_info` of the config.
+    pub fn state_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.state_file = Some(path.into());
+        self
+    }
+
+    /// Records every blocking operation to the JSONL transcript at `path`,
+    /// see [CosmOrc::replay_transcript()]
+    pub fn transcript<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.transcript = Some(path.into());
+        self
+    }
+
+    /// Tracks account sequences locally, enabling the `submit_*()` operations of [CosmOrc]
+    pub fn sequence_manager(mut self, sequences: SequenceManager) -> Self {
+        self.sequences = Some(sequences);
+        self
+    }
+
+    /// Builds a `CosmOrc` using the selected [Backend].
+    ///
+    /// The config is checked with [Config::validate()] first.
+    pub fn build(self) -> Result<CosmOrc<ChainClient>, InitError> {
+        let cfg = self.cfg.as_ref().ok_or(InitError::MissingConfig)?;
+        cfg.validate()?;
+
+        let endpoints = cfg
+            .all_endpoints()
+            .into_iter()
+            .filter(|e| match self.backend {
+                Backend::Auto => true,
+                Backend::CosmosgRPC => matches!(e, Endpoint::Grpc(_)),
+                Backend::TendermintRPC => matches!(e, Endpoint::Rpc(_)),
+            })
+            .collect::<Vec<_>>();
+
+        let (client, endpoint) = ChainClient::connect(&cfg.chain_cfg, &endpoints)?;
+
+        let mut cosm_orc = self.build_with_client(client)?;
+        cosm_orc.set_endpoint(endpoint);
+
+        Ok(cosm_orc)
+    }
+
+    /// Builds a `CosmOrc` using a custom `CosmosClient` backing api, ignoring the selected [Backend].
+    ///
+    /// The config is checked with [Config::validate()] first, except for the endpoints.
+    pub fn build_with_client<C: CosmosClient>(self, client: C) -> Result<CosmOrc<C>, InitError> {
+        let cfg = self.cfg.ok_or(InitError::MissingConfig)?;
+
+        let default_key = match self.default_key {
+            Some(DefaultKey::Key(key)) => Some(key),
+            Some(DefaultKey::Name(name)) => Some(cfg.signing_key(&name)?),
+            None => None,
+        };
+
+        let mut cosm_orc = CosmOrc::with_client(cfg, client, self.gas_profiler)?;
+
+        if let Some(path) = self.state_file {
+            cosm_orc
+                .contract_map
+                .extend_from_file(&path)
+                .map_err(|source| InitError::StateFile {
+                    path: path.clone(),
+                    source,
+                })?;
+            cosm_orc.state_file = Some(path);
+        }
+
+        if let Some(tx_options) = self.tx_options {
+            cosm_orc.tx_options = tx_options;
+        }
+
+        if let Some(label) = self.label {
+            cosm_orc.label = label;
+        }
+
+        if let Some(poll_policy) = self.poll_policy {
+            cosm_orc.poll_policy = poll_policy;
+        }
+
+        if let Some(retry_policy) = self.retry_policy {
+            cosm_orc.retry_policy = retry_policy;
+        }
+
+        if let Some(mut store_policy) = self.store_policy {
+            if store_policy.artifacts == ArtifactFilter::default() {
+                store_policy.artifacts = std::mem::take(&mut cosm_orc.store_policy.artifacts);
+            }
+            cosm_orc.store_policy = store_policy;
+        }
+
+        cosm_orc.default_key = default_key;
+        cosm_orc.sequences = self.sequences;
+        cosm_orc.transcript = self.transcript;
+
+        Ok(cosm_orc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CosmOrcBuilder;
+    use crate::config::cfg::Config;
+    use crate::config::error::KeyError;
+    use crate::config::keys::{KeyConfig, MnemonicSource};
+    use crate::orchestrator::error::InitError;
+    use crate::orchestrator::poll::PollPolicy;
+    use crate::orchestrator::store::{ArtifactFilter, StorePolicy};
+    use assert_matches::assert_matches;
+    use cosm_tome::chain::request::TxOptions;
+    use cosm_tome::clients::client::MockCosmosClient;
+    use cosm_tome::config::cfg::ChainConfig;
+    use cosm_tome::signing_key::key::Key;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    fn cfg() -> Config {
+        Config {
+            chain_cfg: ChainConfig {
+                denom: "utest".to_string(),
+                prefix: "test".to_string(),
+                chain_id: "test-1".to_string(),
+                rpc_endpoint: None,
+                grpc_endpoint: Some("http://localhost:12690".to_string()),
+                derivation_path: "m/44'/118'/0'/0/0".to_string(),
+                gas_price: 0.1,
+                gas_adjustment: 1.5,
+            },
+            contract_deploy_info: HashMap::new(),
+            keys: HashMap::from([(
+                "validator".to_string(),
+                KeyConfig {
+                    source: MnemonicSource::Mnemonic("word1 word2".to_string()),
+                    derivation_path: None,
+                },
+            )]),
+            endpoints: vec![],
+            artifacts: ArtifactFilter::default(),
+        }
+    }
+
+    #[test]
+    fn build_with_options() {
+        let poll_policy = PollPolicy {
+            interval: Duration::from_secs(1),
+            ..Default::default()
+        };
+
+        let cosm_orc = CosmOrcBuilder::new()
+            .config(cfg())
+            .gas_profiler(true)
+            .tx_options(TxOptions {
+                timeout_height: Some(100),
+                fee: None,
+                memo: "test".to_string(),
+            })
+            .default_key_name("validator")
+            .label("{contract_name}-{op_name}")
+            .poll_policy(poll_policy.clone())
+            .build_with_client(MockCosmosClient::new())
+            .unwrap();
+
+        assert!(cosm_orc.gas_profiler_report().is_some());
+        assert_eq!(cosm_orc.tx_options.memo, "test");
+        assert_eq!(cosm_orc.poll_policy(), &poll_policy);
+        assert_eq!(cosm_orc.label, "{contract_name}-{op_name}");
+
+        let key = cosm_orc.default_key().unwrap();
+        assert_eq!(key.name, "validator");
+        assert_eq!(key.key, Key::Mnemonic("word1 word2".to_string()));
+    }
+
+    #[test]
+    fn store_policy_keeps_config_artifacts() {
+        let artifacts = ArtifactFilter {
+            include: vec!["cw20_*".to_string()],
+            ..Default::default()
+        };
+
+        let cosm_orc = CosmOrcBuilder::new()
+            .config(Config {
+                artifacts: artifacts.clone(),
+                ..cfg()
+            })
+            .store_policy(StorePolicy {
+                max_wasm_size: None,
+                ..Default::default()
+            })
+            .build_with_client(MockCosmosClient::new())
+            .unwrap();
+
+        assert_eq!(cosm_orc.store_policy.max_wasm_size, None);
+        assert_eq!(cosm_orc.store_policy.artifacts, artifacts);
+
+        let policy_artifacts = ArtifactFilter {
+            exclude: vec!["*_test*".to_string()],
+            ..Default::default()
+        };
+        let cosm_orc = CosmOrcBuilder::new()
+            .config(Config { artifacts, ..cfg() })
+            .store_policy(StorePolicy {
+                artifacts: policy_artifacts.clone(),
+                ..Default::default()
+            })
+            .build_with_client(MockCosmosClient::new())
+            .unwrap();
+
+        assert_eq!(cosm_orc.store_policy.artifacts, policy_artifacts);
+    }
+
+    #[test]
+    fn build_errors() {
+        assert_matches!(
+            CosmOrcBuilder::new().build().unwrap_err(),
+            InitError::MissingConfig
+        );
+
+        assert_matches!(
+            CosmOrcBuilder::new()
+                .config(cfg())
+                .default_key_name("missing")
+                .build_with_client(MockCosmosClient::new())
+                .unwrap_err(),
+            InitError::KeyError(KeyError::NotFound { .. })
+        );
+    }
+}
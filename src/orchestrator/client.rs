@@ -0,0 +1,164 @@
+use async_trait::async_trait;
+use prost::Message;
+
+use cosm_tome::chain::error::ChainError;
+use cosm_tome::chain::fee::GasInfo;
+use cosm_tome::chain::response::{AsyncChainTxResponse, ChainTxResponse};
+use cosm_tome::clients::client::CosmosClient;
+use cosm_tome::clients::cosmos_grpc::CosmosgRPC;
+use cosm_tome::clients::tendermint_rpc::TendermintRPC;
+use cosm_tome::config::cfg::ChainConfig;
+use cosm_tome::modules::tx::model::{BroadcastMode, RawTx};
+
+use super::failover::{ActiveEndpoint, FailoverClient};
+use crate::config::cfg::Endpoint;
+
+/// Cosmos client backend selected at runtime from the configured endpoints.
+#[derive(Clone, Debug)]
+pub enum ChainClient {
+    CosmosgRPC(CosmosgRPC),
+    TendermintRPC(Box<TendermintRPC>),
+    /// Fails over between multiple endpoints
+    Failover(Box<FailoverClient>),
+}
+
+impl ChainClient {
+    /// Uses the Cosmos gRPC api if `grpc_endpoint` is set, otherwise the tendermint RPC api.
+    pub fn from_config(cfg: &ChainConfig) -> Result<ChainClient, ChainError> {
+        if let Some(grpc_endpoint) = &cfg.grpc_endpoint {
+            return Ok(ChainClient::CosmosgRPC(CosmosgRPC::new(
+                grpc_endpoint.clone(),
+            )));
+        }
+
+        if let Some(rpc_endpoint) = &cfg.rpc_endpoint {
+            let client = TendermintRPC::new(rpc_endpoint)?;
+            return Ok(ChainClient::TendermintRPC(Box::new(client)));
+        }
+
+        Err(ChainError::MissingApiEndpoint {
+            api_type: "cosmos_grpc or tendermint_rpc".to_string(),
+        })
+    }
+
+    /// Connects to a single endpoint
+    pub fn from_endpoint(endpoint: &Endpoint) -> Result<ChainClient, ChainError> {
+        match endpoint {
+            Endpoint::Grpc(url) => Ok(ChainClient::CosmosgRPC(CosmosgRPC::new(url.clone()))),
+            Endpoint::Rpc(url) => Ok(ChainClient::TendermintRPC(Box::new(TendermintRPC::new(
+                url,
+            )?))),
+        }
+    }
+
+    /// Uses `endpoints` in order, failing over to the next healthy one on connection errors.
+    pub fn from_endpoints(
+        cfg: &ChainConfig,
+        endpoints: &[Endpoint],
+    ) -> Result<ChainClient, ChainError> {
+        Ok(Self::connect(cfg, endpoints)?.0)
+    }
+
+    /// Same as [Self::from_endpoints()], also returning a handle to the endpoint in use
+    pub(crate) fn connect(
+        cfg: &ChainConfig,
+        endpoints: &[Endpoint],
+    ) -> Result<(ChainClient, ActiveEndpoint), ChainError> {
+        match endpoints {
+            [endpoint] => Ok((
+                Self::from_endpoint(endpoint)?,
+                ActiveEndpoint::new(vec![endpoint.url().to_string()]),
+            )),
+            _ => {
+                let client = FailoverClient::new(cfg, endpoints)?;
+                let active = client.active_endpoint().clone();
+                Ok((ChainClient::Failover(Box::new(client)), active))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl CosmosClient for ChainClient {
+    async fn query<I, O>(&self, msg: I, path: &str) -> Result<O, ChainError>
+    where
+        Self: Sized,
+        I: Message + Default + tonic::IntoRequest<I> + 'static,
+        O: Message + Default + 'static,
+    {
+        match self {
+            ChainClient::CosmosgRPC(c) => c.query(msg, path).await,
+            ChainClient::TendermintRPC(c) => c.query(msg, path).await,
+            ChainClient::Failover(c) => c.query(msg, path).await,
+        }
+    }
+
+    async fn simulate_tx(&self, tx: &RawTx) -> Result<GasInfo, ChainError> {
+        match self {
+            ChainClient::CosmosgRPC(c) => c.simulate_tx(tx).await,
+            ChainClient::TendermintRPC(c) => c.simulate_tx(tx).await,
+            ChainClient::Failover(c) => c.simulate_tx(tx).await,
+        }
+    }
+
+    async fn broadcast_tx(
+        &self,
+        tx: &RawTx,
+        mode: BroadcastMode,
+    ) -> Result<AsyncChainTxResponse, ChainError> {
+        match self {
+            ChainClient::CosmosgRPC(c) => c.broadcast_tx(tx, mode).await,
+            ChainClient::TendermintRPC(c) => c.broadcast_tx(tx, mode).await,
+            ChainClient::Failover(c) => c.broadcast_tx(tx, mode).await,
+        }
+    }
+
+    async fn broadcast_tx_block(&self, tx: &RawTx) -> Result<ChainTxResponse, ChainError> {
+        match self {
+            ChainClient::CosmosgRPC(c) => c.broadcast_tx_block(tx).await,
+            ChainClient::TendermintRPC(c) => c.broadcast_tx_block(tx).await,
+            ChainClient::Failover(c) => c.broadcast_tx_block(tx).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChainClient;
+    use assert_matches::assert_matches;
+    use cosm_tome::chain::error::ChainError;
+    use cosm_tome::config::cfg::ChainConfig;
+
+    fn cfg(grpc_endpoint: Option<&str>, rpc_endpoint: Option<&str>) -> ChainConfig {
+        ChainConfig {
+            denom: "utest".to_string(),
+            prefix: "test".to_string(),
+            chain_id: "test-1".to_string(),
+            rpc_endpoint: rpc_endpoint.map(Into::into),
+            grpc_endpoint: grpc_endpoint.map(Into::into),
+            derivation_path: "m/44'/118'/0'/0/0".to_string(),
+            gas_price: 0.1,
+            gas_adjustment: 1.5,
+        }
+    }
+
+    #[test]
+    fn selects_backend_from_config() {
+        assert_matches!(
+            ChainClient::from_config(&cfg(
+                Some("http://localhost:9090"),
+                Some("http://localhost:26657")
+            ))
+            .unwrap(),
+            ChainClient::CosmosgRPC(_)
+        );
+        assert_matches!(
+            ChainClient::from_config(&cfg(None, Some("http://localhost:26657"))).unwrap(),
+            ChainClient::TendermintRPC(_)
+        );
+        assert_matches!(
+            ChainClient::from_config(&cfg(None, None)).unwrap_err(),
+            ChainError::MissingApiEndpoint { .. }
+        );
+    }
+}
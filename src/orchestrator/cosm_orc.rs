@@ -1,47 +1,95 @@
-use log::{debug, info};
+use futures::future::{self, Either};
+use futures::stream::{FuturesUnordered, StreamExt};
 use serde::Serialize;
-use std::env::consts::ARCH;
-use std::ffi::OsStr;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt::{self, Debug};
 use std::fs;
 use std::future::Future;
 use std::panic::Location;
-use std::path::Path;
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tendermint_rpc::query::{EventType, Query};
 use tokio::time::{self, timeout as _timeout};
+use tracing::{debug, field, info, info_span, warn};
 
-use cosm_tome::chain::coin::Coin;
-use cosm_tome::chain::error::ChainError;
-use cosm_tome::chain::request::TxOptions;
+use cosm_tome::chain::coin::{Coin, Denom};
+use cosm_tome::chain::msg::Msg;
+use cosm_tome::chain::request::{PageID, PaginationRequest, TxOptions};
+use cosm_tome::chain::response::{AsyncChainTxResponse, ChainTxResponse};
 use cosm_tome::clients::client::{CosmTome, CosmosClient};
 use cosm_tome::clients::cosmos_grpc::CosmosgRPC;
 use cosm_tome::clients::tendermint_rpc::TendermintRPC;
+use cosm_tome::config::cfg::ChainConfig;
+use cosm_tome::modules::auth::error::AccountError;
 use cosm_tome::modules::auth::model::Address;
+use cosm_tome::modules::bank::error::BankError;
+use cosm_tome::modules::bank::model::{SendRequest, SendResponse};
 use cosm_tome::modules::cosmwasm::error::CosmwasmError;
 use cosm_tome::modules::cosmwasm::model::{
     ExecRequest, ExecResponse, InstantiateRequest, InstantiateResponse, MigrateRequest,
     MigrateResponse, QueryResponse, StoreCodeRequest, StoreCodeResponse,
 };
 use cosm_tome::modules::tendermint::error::TendermintError;
+use cosm_tome::modules::tx::error::TxError;
 use cosm_tome::signing_key::key::SigningKey;
 
-use super::error::{PollBlockError, ProcessError, StoreError};
-use super::ExecReq;
+use super::error::{
+    ContractMapError, InitError, LoadTestError, PollBlockError, PollQueryError, ProcessError,
+    StoreError, SubscribeError, TranscriptError,
+};
+use super::{ExecReq, KeyRef, SendReq};
 use crate::config::cfg::Config;
+use crate::config::error::KeyError;
+use crate::config::keys::KeyConfig;
+use crate::orchestrator::accounts::{
+    AccountFactory, Refund, RefundPlan, TestAccount, TestAccounts,
+};
+use crate::orchestrator::builder::CosmOrcBuilder;
+use crate::orchestrator::client::ChainClient;
 use crate::orchestrator::deploy::ContractMap;
-use crate::orchestrator::gas_profiler::{CommandType, GasProfiler, Report};
+use crate::orchestrator::failover::ActiveEndpoint;
+use crate::orchestrator::gas_profiler::{fee_paid, CommandType, FeeReport, GasProfiler, Report};
+use crate::orchestrator::load::{LoadReport, LoadStats, LoadTest};
+use crate::orchestrator::poll::{PollPolicy, Poller};
+use crate::orchestrator::retry::RetryPolicy;
+use crate::orchestrator::sequence::SequenceManager;
+use crate::orchestrator::signer::Signer;
+use crate::orchestrator::store::{StorePolicy, StoreResponse};
+use crate::orchestrator::subscribe::{ChainEvent, EventFilter, EventStream};
+use crate::orchestrator::trace::{op_span, OpSpan};
+use crate::orchestrator::transcript::{
+    self, TranscriptEntry, TranscriptExec, TranscriptOp, TranscriptSend, TxRecord,
+};
+use crate::orchestrator::wasm::WasmInfo;
 use crate::orchestrator::AccessConfig;
 
 #[cfg(feature = "optimize")]
 use super::error::OptimizeError;
 
+/// Default label of instantiated contracts
+pub const DEFAULT_LABEL: &str = "cosm-orc";
+
 /// Stores cosmwasm contracts and executes their messages against the configured chain.
 #[derive(Clone)]
 pub struct CosmOrc<C: CosmosClient> {
     pub contract_map: ContractMap,
     pub client: CosmTome<C>,
-    gas_profiler: Option<GasProfiler>,
-    tx_options: TxOptions,
+    pub(crate) poll_policy: PollPolicy,
+    /// Controls how failed store, instantiate, execute, migrate, send and query operations are retried
+    pub retry_policy: RetryPolicy,
+    /// Controls how wasm files are checked by `store_contracts()` before they are uploaded
+    pub store_policy: StorePolicy,
+    pub(crate) chain_cfg: ChainConfig,
+    pub(crate) keys: HashMap<String, KeyConfig>,
+    pub(crate) gas_profiler: Option<GasProfiler>,
+    pub(crate) tx_options: TxOptions,
+    pub(crate) default_key: Option<SigningKey>,
+    pub(crate) label: String,
+    pub(crate) state_file: Option<PathBuf>,
+    pub(crate) sequences: Option<SequenceManager>,
+    pub(crate) endpoint: Option<ActiveEndpoint>,
+    pub(crate) transcript: Option<PathBuf>,
 }
 
 impl<C: CosmosClient> Debug for CosmOrc<C> {
@@ -53,41 +101,200 @@ impl<C: CosmosClient> Debug for CosmOrc<C> {
 impl CosmOrc<CosmosgRPC> {
     /// Creates a CosmOrc object from the supplied Config, using the CosmosgRPC backing api
     /// optionally using a gas profiler
-    pub fn new(cfg: Config, use_gas_profiler: bool) -> Result<CosmOrc<CosmosgRPC>, ChainError> {
-        let gas_profiler = if use_gas_profiler {
-            Some(GasProfiler::new())
-        } else {
-            None
-        };
+    ///
+    /// The config is checked with [Config::validate()] first.
+    pub fn new(cfg: Config, use_gas_profiler: bool) -> Result<CosmOrc<CosmosgRPC>, InitError> {
+        cfg.validate()?;
+        let client = CosmTome::with_cosmos_grpc(cfg.chain_cfg.clone())?;
 
-        Ok(CosmOrc {
-            contract_map: ContractMap::new(cfg.contract_deploy_info),
-            client: CosmTome::with_cosmos_grpc(cfg.chain_cfg)?,
-            gas_profiler,
-            tx_options: TxOptions::default(),
-        })
+        Ok(Self::from_parts(cfg, client, use_gas_profiler))
     }
 }
 
 impl CosmOrc<TendermintRPC> {
     /// Creates a CosmOrc object from the supplied Config, using the tendermint RPC backing api
     /// optionally using a gas profiler
+    ///
+    /// The config is checked with [Config::validate()] first.
     pub fn new_tendermint_rpc(
         cfg: Config,
         use_gas_profiler: bool,
-    ) -> Result<CosmOrc<TendermintRPC>, ChainError> {
+    ) -> Result<CosmOrc<TendermintRPC>, InitError> {
+        cfg.validate()?;
+        let client = CosmTome::with_tendermint_rpc(cfg.chain_cfg.clone())?;
+
+        Ok(Self::from_parts(cfg, client, use_gas_profiler))
+    }
+}
+
+impl CosmOrc<ChainClient> {
+    /// Returns a builder for configuring every `CosmOrc` option.
+    pub fn builder() -> CosmOrcBuilder {
+        CosmOrcBuilder::new()
+    }
+
+    /// Creates a CosmOrc object from the supplied Config, connecting to the endpoints returned by
+    /// [Config::all_endpoints()] in order and failing over to the next healthy one on connection errors.
+    ///
+    /// The config is checked with [Config::validate()] first.
+    pub fn from_config(
+        cfg: Config,
+        use_gas_profiler: bool,
+    ) -> Result<CosmOrc<ChainClient>, InitError> {
+        cfg.validate()?;
+        let (client, endpoint) = ChainClient::connect(&cfg.chain_cfg, &cfg.all_endpoints())?;
+
+        let mut cosm_orc = Self::from_parts(
+            cfg.clone(),
+            CosmTome::new(cfg.chain_cfg, client),
+            use_gas_profiler,
+        );
+        cosm_orc.set_endpoint(endpoint);
+
+        Ok(cosm_orc)
+    }
+}
+
+impl<C: CosmosClient> CosmOrc<C> {
+    /// Creates a CosmOrc object from the supplied Config, using a custom `CosmosClient` backing api
+    /// optionally using a gas profiler
+    ///
+    /// The config is checked with [Config::validate()] first, except for the endpoints
+    /// which are not needed since `client` handles the chain connection.
+    pub fn with_client(cfg: Config, client: C, use_gas_profiler: bool) -> Result<Self, InitError> {
+        cfg.validate_with(false)?;
+
+        let client = CosmTome::new(cfg.chain_cfg.clone(), client);
+
+        Ok(Self::from_parts(cfg, client, use_gas_profiler))
+    }
+
+    fn from_parts(cfg: Config, client: CosmTome<C>, use_gas_profiler: bool) -> Self {
         let gas_profiler = if use_gas_profiler {
             Some(GasProfiler::new())
         } else {
             None
         };
 
-        Ok(CosmOrc {
+        CosmOrc {
             contract_map: ContractMap::new(cfg.contract_deploy_info),
-            client: CosmTome::with_tendermint_rpc(cfg.chain_cfg)?,
+            client,
+            poll_policy: PollPolicy::default(),
+            retry_policy: RetryPolicy::default(),
+            store_policy: StorePolicy {
+                artifacts: cfg.artifacts,
+                ..StorePolicy::default()
+            },
+            chain_cfg: cfg.chain_cfg,
+            keys: cfg.keys,
             gas_profiler,
             tx_options: TxOptions::default(),
-        })
+            default_key: None,
+            label: DEFAULT_LABEL.to_string(),
+            state_file: None,
+            sequences: None,
+            endpoint: None,
+            transcript: None,
+        }
+    }
+
+    /// Controls how often the chain is queried by `poll_for_n_blocks()` and `poll_for_n_secs()`
+    pub fn poll_policy(&self) -> &PollPolicy {
+        &self.poll_policy
+    }
+
+    /// Sets the policy used by `poll_for_n_blocks()` and `poll_for_n_secs()`
+    pub fn set_poll_policy(&mut self, poll_policy: PollPolicy) {
+        self.poll_policy = poll_policy;
+    }
+
+    /// Key used by operations called with `()` or `KeyRef::Default` as key, or through [Self::signer()]
+    pub fn default_key(&self) -> Option<&SigningKey> {
+        self.default_key.as_ref()
+    }
+
+    /// Sets the key used by operations called with `()` or `KeyRef::Default` as key, or through [Self::signer()]
+    pub fn set_default_key(&mut self, key: Option<SigningKey>) {
+        self.default_key = key;
+    }
+
+    /// Local account sequence tracking used by the `submit_*()` operations, if enabled
+    pub fn sequence_manager(&self) -> Option<&SequenceManager> {
+        self.sequences.as_ref()
+    }
+
+    /// Enables or disables local account sequence tracking for the `submit_*()` operations
+    pub fn set_sequence_manager(&mut self, sequences: Option<SequenceManager>) {
+        self.sequences = sequences;
+    }
+
+    /// Url of the endpoint currently serving requests
+    pub fn endpoint(&self) -> Option<&str> {
+        self.endpoint.as_ref().map(|e| e.url())
+    }
+
+    pub(crate) fn set_endpoint(&mut self, endpoint: ActiveEndpoint) {
+        if let Some(p) = self.gas_profiler.as_mut() {
+            p.set_endpoint(Some(endpoint.clone()));
+        }
+        self.endpoint = Some(endpoint);
+    }
+
+    /// JSONL file that every blocking operation is recorded to, if enabled
+    pub fn transcript(&self) -> Option<&Path> {
+        self.transcript.as_deref()
+    }
+
+    /// Starts or stops recording operations to a JSONL transcript.
+    ///
+    /// Entries are appended to `path` if it already exists. See [Self::replay_transcript()].
+    pub fn set_transcript(&mut self, path: Option<PathBuf>) {
+        self.transcript = path;
+    }
+
+    /// Returns a handle that signs every operation with the default key.
+    pub fn signer(&mut self) -> Signer<'_, C> {
+        Signer::new(self, KeyRef::Default)
+    }
+
+    /// Returns a handle that signs every operation with `key`.
+    ///
+    /// # Arguments
+    /// * `key` - SigningKey, or the name of a key in `Config::keys`.
+    pub fn with_signer<'a, K>(&'a mut self, key: K) -> Signer<'a, C>
+    where
+        K: Into<KeyRef<'a>>,
+    {
+        Signer::new(self, key.into())
+    }
+}
+
+impl CosmOrc<TendermintRPC> {
+    /// Subscribes to `NewBlock` events over the tendermint websocket api.
+    ///
+    /// The websocket endpoint is derived from the configured `rpc_endpoint`.
+    pub fn subscribe_new_blocks(&self) -> Result<EventStream, SubscribeError> {
+        self.event_stream(EventType::NewBlock.into())
+    }
+
+    /// Subscribes to `Tx` events matching `filter` over the tendermint websocket api.
+    ///
+    /// The websocket endpoint is derived from the configured `rpc_endpoint`.
+    pub fn subscribe_txs(&self, filter: &EventFilter) -> Result<EventStream, SubscribeError> {
+        self.event_stream(filter.query())
+    }
+
+    /// Subscribes to `Tx` events emitted by the deployed smart contract `contract_name`.
+    ///
+    /// # Errors
+    /// * If `contract_name` has not been instantiated via [Self::instantiate()]
+    ///   `cosm_orc::orchestrator::error::ContractMapError::NotDeployed` is thrown.
+    pub fn subscribe_contract<S: Into<String>>(
+        &self,
+        contract_name: S,
+    ) -> Result<EventStream, SubscribeError> {
+        let addr = self.contract_map.address(&contract_name.into())?;
+        self.subscribe_txs(&EventFilter::contract(addr))
     }
 }
 
@@ -102,8 +309,6 @@ impl<C: CosmosClient> CosmOrc<C> {
         Ok(())
     }
 
-    // TODO: Implement store_contract() that stores a single contract
-
     /// Uploads the optimized contracts in `wasm_dir` to the configured chain
     /// saving the resulting contract ids in `contract_map`.
     ///
@@ -112,67 +317,116 @@ impl<C: CosmosClient> CosmOrc<C> {
     ///
     /// If you have not built and optimized the wasm files, use [Self::optimize_contracts()]
     ///
-    /// NOTE: Currently, the name of the wasm files in `wasm_dir` will be
+    /// NOTE: By default, the name of the wasm files in `wasm_dir` will be
     /// used as the `contract_name` parameter to `instantiate()`, `query()` and `execute()`.
+    /// Files are stored in file name order, filtered and renamed by `store_policy.artifacts`.
+    ///
+    /// Each wasm file is checked against the `store_policy` before it is broadcasted,
+    /// and its size and checksum are returned in its [StoreResponse].
     #[track_caller]
-    pub fn store_contracts(
+    pub fn store_contracts<'k, K>(
         &mut self,
         wasm_dir: &str,
-        key: &SigningKey,
+        key: K,
         instantiate_perms: Option<AccessConfig>,
-    ) -> Result<Vec<StoreCodeResponse>, StoreError> {
+    ) -> Result<Vec<StoreResponse>, StoreError>
+    where
+        K: Into<KeyRef<'k>>,
+    {
+        let key = &self.resolve_key(key.into())?;
         let mut responses = vec![];
-        let wasm_path = Path::new(wasm_dir);
-
-        for wasm in fs::read_dir(wasm_path).map_err(StoreError::wasmdir)? {
-            let wasm_path = wasm?.path();
-            if wasm_path.extension() == Some(OsStr::new("wasm")) {
-                info!("Storing {:?}", wasm_path);
-
-                let wasm = fs::read(&wasm_path).map_err(StoreError::wasmfile)?;
-
-                let res = tokio_block(async {
-                    self.client
-                        .wasm_store(
-                            StoreCodeRequest {
-                                wasm_data: wasm,
-                                instantiate_perms: instantiate_perms.clone(),
-                            },
-                            key,
-                            &self.tx_options,
-                        )
-                        .await
-                })?;
-
-                let mut contract = wasm_path
-                    .file_stem()
-                    .ok_or(StoreError::InvalidWasmFileName)?
-                    .to_str()
-                    .ok_or(StoreError::InvalidWasmFileName)?;
-
-                // parse out OS architecture if optimizoor was used:
-                let arch_suffix = format!("-{ARCH}");
-                if contract.to_string().ends_with(&arch_suffix) {
-                    contract = contract.trim_end_matches(&arch_suffix);
-                }
+        let artifacts = self.store_policy.artifacts.select(Path::new(wasm_dir))?;
 
-                self.contract_map
-                    .register_contract(contract.to_string(), res.code_id);
-
-                if let Some(p) = &mut self.gas_profiler {
-                    p.instrument(
-                        contract.to_string(),
-                        "Store".to_string(),
-                        CommandType::Store,
-                        &res.res,
-                        Location::caller(),
-                    );
-                }
+        for (contract, wasm_path) in artifacts {
+            let res = self.store_contract(&contract, &wasm_path, key, instantiate_perms.clone())?;
+            responses.push(res);
+        }
+        Ok(responses)
+    }
 
-                responses.push(res);
+    /// Stores the wasm file at `wasm_path` as `contract`
+    #[track_caller]
+    fn store_contract(
+        &mut self,
+        contract: &str,
+        wasm_path: &Path,
+        key: &SigningKey,
+        instantiate_perms: Option<AccessConfig>,
+    ) -> Result<StoreResponse, StoreError> {
+        let span = OpSpan::enter(op_span!("store", contract = %contract));
+
+        let wasm = fs::read(wasm_path).map_err(StoreError::wasmfile)?;
+        let wasm_info = WasmInfo::parse(&wasm).map_err(|e| StoreError::InvalidWasm {
+            contract: contract.to_string(),
+            source: e,
+        })?;
+
+        info!(
+            wasm_path = %wasm_path.display(),
+            size = wasm_info.size,
+            checksum = %wasm_info.checksum,
+            "storing contract"
+        );
+
+        self.store_policy.check(contract, &wasm, &wasm_info)?;
+
+        let res = tokio_block(async {
+            let sender = key.to_addr(&self.chain_cfg.prefix).await?;
+            let req = StoreCodeRequest {
+                wasm_data: wasm.clone(),
+                instantiate_perms: instantiate_perms.clone(),
             }
+            .to_proto(sender.clone())?;
+
+            let res = self.broadcast("store", vec![req], &sender, key).await?;
+
+            let code_id = res
+                .find_event_tags("store_code".to_string(), "code_id".to_string())
+                .first()
+                .and_then(|t| t.value.parse().ok())
+                .ok_or(CosmwasmError::MissingEvent)?;
+
+            Ok::<_, CosmwasmError>(StoreCodeResponse { code_id, res })
+        });
+
+        self.record(
+            || {
+                Ok(TranscriptOp::Store {
+                    contract: contract.to_string(),
+                    wasm_path: wasm_path.to_path_buf(),
+                    instantiate_perms: instantiate_perms.clone(),
+                })
+            },
+            Some(key),
+            res.as_ref()
+                .map(|r| Some(&r.res))
+                .map_err(|e| e.to_string()),
+        );
+        let res = res?;
+        span.record_tx(&res.res);
+
+        self.contract_map
+            .register_contract(contract.to_string(), res.code_id);
+        self.save_state();
+
+        self.instrument(
+            contract.to_string(),
+            "Store".to_string(),
+            CommandType::Store,
+            &res.res,
+            key,
+        );
+
+        if let Some(p) = &mut self.gas_profiler {
+            p.record_wasm(contract, wasm_info.clone());
         }
-        Ok(responses)
+
+        Ok(StoreResponse {
+            contract: contract.to_string(),
+            code_id: res.code_id,
+            wasm: wasm_info,
+            res: res.res,
+        })
     }
 
     /// Initializes a smart contract against the configured chain.
@@ -181,7 +435,7 @@ impl<C: CosmosClient> CosmOrc<C> {
     /// * `contract_name` - Stored smart contract name for the corresponding `msg`.
     /// * `msg` - InstantiateMsg that `contract_name` supports.
     /// * `op_name` - Human readable operation name for profiling bookkeeping usage.
-    /// * `key` - SigningKey used to sign the tx.
+    /// * `key` - SigningKey, the name of a key in `Config::keys`, or `()` for the default key, used to sign the tx.
     /// * `admin` - Optional admin address for contract migration.
     /// * `funds` - Optional tokens transferred to the contract after instantiation.
     ///
@@ -189,54 +443,89 @@ impl<C: CosmosClient> CosmOrc<C> {
     /// * If `contract_name` has not been configured in `Config::code_ids` or stored through
     ///   [Self::store_contracts()] `cosm_orc::orchestrator::error::ContractMapError::NotStored` is thrown.
     #[track_caller]
-    pub fn instantiate<S, T>(
+    pub fn instantiate<'k, S, T, K>(
         &mut self,
         contract_name: S,
         op_name: S,
         msg: &T,
-        key: &SigningKey,
+        key: K,
         admin: Option<Address>,
         funds: Vec<Coin>,
     ) -> Result<InstantiateResponse, ProcessError>
     where
         S: Into<String>,
         T: Serialize,
+        K: Into<KeyRef<'k>>,
     {
+        let key = &self.resolve_key(key.into())?;
         let contract_name = contract_name.into();
         let op_name = op_name.into();
 
         let code_id = self.contract_map.code_id(&contract_name)?;
 
+        let span = OpSpan::enter(op_span!(
+            "instantiate",
+            contract = %contract_name,
+            op_name = %op_name,
+            code_id
+        ));
+
+        let label = self.label(&contract_name, &op_name);
+
         let res = tokio_block(async {
-            self.client
-                .wasm_instantiate(
-                    InstantiateRequest {
-                        code_id,
-                        msg,
-                        label: "cosm-orc".to_string(),
-                        admin,
-                        funds,
-                    },
-                    key,
-                    &self.tx_options,
-                )
-                .await
-        })?;
+            let sender = key.to_addr(&self.chain_cfg.prefix).await?;
+            let req = InstantiateRequest {
+                code_id,
+                msg,
+                label: label.clone(),
+                admin: admin.clone(),
+                funds: funds.clone(),
+            }
+            .to_proto(sender.clone())?;
 
-        self.contract_map
-            .add_address(&contract_name, res.address.clone())?;
+            let res = self
+                .broadcast("instantiate", vec![req], &sender, key)
+                .await?;
 
-        if let Some(p) = &mut self.gas_profiler {
-            p.instrument(
-                contract_name,
-                op_name,
-                CommandType::Instantiate,
-                &res.res,
-                Location::caller(),
-            );
-        }
+            let address = res
+                .find_event_tags("instantiate".to_string(), "_contract_address".to_string())
+                .first()
+                .ok_or(CosmwasmError::MissingEvent)?
+                .value
+                .parse()?;
+
+            Ok::<_, CosmwasmError>(InstantiateResponse { address, res })
+        });
+
+        self.record(
+            || {
+                Ok(TranscriptOp::Instantiate {
+                    contract: contract_name.clone(),
+                    op_name: op_name.clone(),
+                    msg: serde_json::to_value(msg)?,
+                    admin: admin.as_ref().map(|a| a.to_string()),
+                    funds: funds.clone(),
+                })
+            },
+            Some(key),
+            res.as_ref()
+                .map(|r| Some(&r.res))
+                .map_err(|e| e.to_string()),
+        );
+        let res = res?;
+        span.record_tx(&res.res);
 
-        debug!("{:?}", res.res);
+        self.contract_map
+            .add_address(&contract_name, res.address.clone())?;
+        self.save_state();
+
+        self.instrument(
+            contract_name,
+            op_name,
+            CommandType::Instantiate,
+            &res.res,
+            key,
+        );
 
         Ok(res)
     }
@@ -247,55 +536,73 @@ impl<C: CosmosClient> CosmOrc<C> {
     /// * `contract_name` - Deployed smart contract name for the corresponding `msg`.
     /// * `msg` - ExecuteMsg that `contract_name` supports.
     /// * `op_name` - Human readable operation name for profiling bookkeeping usage.
-    /// * `key` - SigningKey used to sign the tx.
+    /// * `key` - SigningKey, the name of a key in `Config::keys`, or `()` for the default key, used to sign the tx.
     /// * `funds` - Optional tokens transferred to the contract after execution.
     ///
     /// # Errors
     /// * If `contract_name` has not been instantiated via [Self::instantiate()]
     ///   `cosm_orc::orchestrator::error::ContractMapError::NotDeployed` is thrown.
     #[track_caller]
-    pub fn execute<S, T>(
+    pub fn execute<'k, S, T, K>(
         &mut self,
         contract_name: S,
         op_name: S,
         msg: &T,
-        key: &SigningKey,
+        key: K,
         funds: Vec<Coin>,
     ) -> Result<ExecResponse, ProcessError>
     where
         S: Into<String>,
         T: Serialize,
+        K: Into<KeyRef<'k>>,
     {
+        let key = &self.resolve_key(key.into())?;
         let contract_name = contract_name.into();
         let op_name = op_name.into();
 
         let addr = self.contract_map.address(&contract_name)?;
+        let address: Address = addr
+            .parse()
+            .map_err(|e| ProcessError::CosmwasmError(CosmwasmError::AccountError(e)))?;
+
+        let span = OpSpan::enter(op_span!(
+            "execute",
+            contract = %contract_name,
+            op_name = %op_name
+        ));
 
         let res = tokio_block(async {
-            self.client
-                .wasm_execute(
-                    ExecRequest {
-                        address: addr.parse()?,
-                        msg,
-                        funds,
-                    },
-                    key,
-                    &self.tx_options,
-                )
-                .await
-        })?;
+            let sender = key.to_addr(&self.chain_cfg.prefix).await?;
+            let req = ExecRequest {
+                address: address.clone(),
+                msg,
+                funds: funds.clone(),
+            }
+            .to_proto(sender.clone())?;
 
-        if let Some(p) = &mut self.gas_profiler {
-            p.instrument(
-                contract_name,
-                op_name,
-                CommandType::Execute,
-                &res.res,
-                Location::caller(),
-            );
-        }
+            let res = self.broadcast("execute", vec![req], &sender, key).await?;
+
+            Ok::<_, CosmwasmError>(ExecResponse { res })
+        });
+
+        self.record(
+            || {
+                Ok(TranscriptOp::Execute {
+                    contract: contract_name.clone(),
+                    op_name: op_name.clone(),
+                    msg: serde_json::to_value(msg)?,
+                    funds: funds.clone(),
+                })
+            },
+            Some(key),
+            res.as_ref()
+                .map(|r| Some(&r.res))
+                .map_err(|e| e.to_string()),
+        );
+        let res = res?;
+        span.record_tx(&res.res);
 
-        debug!("{:?}", res.res);
+        self.instrument(contract_name, op_name, CommandType::Execute, &res.res, key);
 
         Ok(res)
     }
@@ -305,56 +612,194 @@ impl<C: CosmosClient> CosmOrc<C> {
     /// # Arguments
     /// * `op_name` - Human readable operation name for profiling bookkeeping usage.
     /// * `reqs` - Wasm execute msgs to batch into a single a tx.
-    /// * `key` - SigningKey used to sign the tx.
+    /// * `key` - SigningKey, the name of a key in `Config::keys`, or `()` for the default key, used to sign the tx.
     ///
     /// # Errors
     /// * If `contract_name` has not been instantiated via [Self::instantiate()]
     ///   `cosm_orc::orchestrator::error::ContractMapError::NotDeployed` is thrown.
     #[track_caller]
-    pub fn execute_batch<S, I>(
+    pub fn execute_batch<'k, S, I, K>(
         &mut self,
         op_name: S,
         reqs: I,
-        key: &SigningKey,
+        key: K,
     ) -> Result<ExecResponse, ProcessError>
     where
         S: Into<String>,
         I: IntoIterator<Item = ExecReq>,
+        K: Into<KeyRef<'k>>,
     {
+        let key = &self.resolve_key(key.into())?;
         let op_name = op_name.into();
 
-        let reqs = reqs
-            .into_iter()
-            .map(|r| -> Result<_, ProcessError> {
+        let reqs = reqs.into_iter().collect::<Vec<_>>();
+        let exec_reqs = self.exec_requests(&reqs)?;
+
+        let span = OpSpan::enter(op_span!(
+            "execute_batch",
+            op_name = %op_name,
+            msgs = reqs.len()
+        ));
+
+        let res = tokio_block(async {
+            let sender = key.to_addr(&self.chain_cfg.prefix).await?;
+            let msgs = exec_reqs
+                .into_iter()
+                .map(|r| r.to_proto(sender.clone()))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let res = self.broadcast("execute_batch", msgs, &sender, key).await?;
+
+            Ok::<_, CosmwasmError>(ExecResponse { res })
+        });
+
+        self.record(
+            || {
+                Ok(TranscriptOp::ExecuteBatch {
+                    op_name: op_name.clone(),
+                    reqs: reqs
+                        .iter()
+                        .map(|r| {
+                            Ok(TranscriptExec {
+                                contract: r.contract_name.clone(),
+                                msg: serde_json::to_value(&r.msg)?,
+                                funds: r.funds.clone(),
+                            })
+                        })
+                        .collect::<Result<_, serde_json::Error>>()?,
+                })
+            },
+            Some(key),
+            res.as_ref()
+                .map(|r| Some(&r.res))
+                .map_err(|e| e.to_string()),
+        );
+        let res = res?;
+        span.record_tx(&res.res);
+
+        self.instrument(
+            "multiple_contracts".to_string(),
+            op_name,
+            CommandType::Execute,
+            &res.res,
+            key,
+        );
+
+        Ok(res)
+    }
+
+    fn exec_requests(
+        &self,
+        reqs: &[ExecReq],
+    ) -> Result<Vec<ExecRequest<serde_json::Value>>, ProcessError> {
+        reqs.iter()
+            .map(|r| {
                 let addr = self.contract_map.address(&r.contract_name)?;
 
                 Ok(ExecRequest {
                     address: addr
                         .parse()
                         .map_err(|e| ProcessError::CosmwasmError(CosmwasmError::AccountError(e)))?,
-                    msg: r.msg,
-                    funds: r.funds,
+                    msg: serde_json::to_value(&r.msg).map_err(ProcessError::json)?,
+                    funds: r.funds.clone(),
                 })
             })
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect()
+    }
+
+    /// Broadcasts a smart contract execution without waiting for it to be committed,
+    /// so several txs signed by the same key can land in the same block.
+    ///
+    /// The tx is signed with the sequence tracked by the [SequenceManager], and re-signed if the
+    /// chain rejects it with `account sequence mismatch`. Only the `CheckTx` result is returned,
+    /// so the gas profiler is not updated.
+    ///
+    /// Wait for submitted txs to be committed before running blocking operations with the same key.
+    ///
+    /// # Arguments
+    /// * `contract_name` - Deployed smart contract name for the corresponding `msg`.
+    /// * `msg` - ExecuteMsg that `contract_name` supports.
+    /// * `key` - SigningKey, the name of a key in `Config::keys`, or `()` for the default key, used to sign the tx.
+    /// * `funds` - Optional tokens transferred to the contract after execution.
+    ///
+    /// # Errors
+    /// * If the sequence manager was not enabled with [Self::set_sequence_manager()]
+    ///   `cosm_orc::orchestrator::error::ProcessError::SequenceManagerDisabled` is thrown.
+    pub fn submit_execute<'k, S, T, K>(
+        &mut self,
+        contract_name: S,
+        msg: &T,
+        key: K,
+        funds: Vec<Coin>,
+    ) -> Result<AsyncChainTxResponse, ProcessError>
+    where
+        S: Into<String>,
+        T: Serialize,
+        K: Into<KeyRef<'k>>,
+    {
+        let key = &self.resolve_key(key.into())?;
+        let addr = self.contract_map.address(&contract_name.into())?;
+
+        let req = ExecRequest {
+            address: addr
+                .parse()
+                .map_err(|e| ProcessError::CosmwasmError(CosmwasmError::AccountError(e)))?,
+            msg,
+            funds,
+        };
+
+        self.submit_exec(vec![req], key)
+    }
+
+    /// Same as [Self::submit_execute()], batching multiple smart contract operations into a single tx.
+    ///
+    /// # Arguments
+    /// * `reqs` - Wasm execute msgs to batch into a single a tx.
+    /// * `key` - SigningKey, the name of a key in `Config::keys`, or `()` for the default key, used to sign the tx.
+    pub fn submit_execute_batch<'k, I, K>(
+        &mut self,
+        reqs: I,
+        key: K,
+    ) -> Result<AsyncChainTxResponse, ProcessError>
+    where
+        I: IntoIterator<Item = ExecReq>,
+        K: Into<KeyRef<'k>>,
+    {
+        let key = &self.resolve_key(key.into())?;
+        let reqs = self.exec_requests(&reqs.into_iter().collect::<Vec<_>>())?;
+
+        self.submit_exec(reqs, key)
+    }
+
+    fn submit_exec<S: Serialize>(
+        &mut self,
+        reqs: Vec<ExecRequest<S>>,
+        key: &SigningKey,
+    ) -> Result<AsyncChainTxResponse, ProcessError> {
+        let sequences = self
+            .sequences
+            .as_mut()
+            .ok_or(ProcessError::SequenceManagerDisabled)?;
+        let client = &self.client;
+        let cfg = &self.chain_cfg;
+        let tx_options = &self.tx_options;
 
         let res = tokio_block(async {
-            self.client
-                .wasm_execute_batch(reqs, key, &self.tx_options)
-                .await
-        })?;
+            let sender = key.to_addr(&cfg.prefix).await?;
 
-        if let Some(p) = &mut self.gas_profiler {
-            p.instrument(
-                "multiple_contracts".to_string(),
-                op_name,
-                CommandType::Execute,
-                &res.res,
-                Location::caller(),
-            );
-        }
+            let msgs = reqs
+                .into_iter()
+                .map(|r| r.to_proto(sender.clone()))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let res = sequences
+                .submit(client, cfg, msgs, &sender, key, tx_options)
+                .await?;
+
+            Ok::<_, CosmwasmError>(res)
+        })?;
 
-        debug!("{:?}", res.res);
+        debug!("{:?}", res);
 
         Ok(res)
     }
@@ -378,9 +823,28 @@ impl<C: CosmosClient> CosmOrc<C> {
 
         let addr = self.contract_map.address(&contract_name)?;
 
-        let res = tokio_block(async { self.client.wasm_query(addr.parse()?, msg).await })?;
+        let address: Address = addr
+            .parse()
+            .map_err(|e| ProcessError::CosmwasmError(CosmwasmError::AccountError(e)))?;
+
+        let _span = OpSpan::enter(op_span!("query", contract = %contract_name));
+
+        let res = tokio_block(
+            self.retry_policy
+                .run("query", || self.client.wasm_query(address.clone(), msg)),
+        );
 
-        debug!("{:?}", res.res);
+        self.record(
+            || {
+                Ok(TranscriptOp::Query {
+                    contract: contract_name.clone(),
+                    msg: serde_json::to_value(msg)?,
+                })
+            },
+            None,
+            res.as_ref().map(|_| None).map_err(|e| e.to_string()),
+        );
+        let res = res?;
 
         Ok(res)
     }
@@ -392,55 +856,511 @@ impl<C: CosmosClient> CosmOrc<C> {
     /// * `new_code_id` - New code id that we will migrate `contract_name` to.
     /// * `msg` - MigrateMsg that `contract_name` supports.
     /// * `op_name` - Human readable operation name for profiling bookkeeping usage.
-    /// * `key` - SigningKey used to sign the tx.
+    /// * `key` - SigningKey, the name of a key in `Config::keys`, or `()` for the default key, used to sign the tx.
     #[track_caller]
-    pub fn migrate<S, T>(
+    pub fn migrate<'k, S, T, K>(
         &mut self,
         contract_name: S,
         new_code_id: u64,
         op_name: S,
         msg: &T,
-        key: &SigningKey,
+        key: K,
     ) -> Result<MigrateResponse, ProcessError>
     where
         S: Into<String>,
         T: Serialize,
+        K: Into<KeyRef<'k>>,
     {
+        let key = &self.resolve_key(key.into())?;
         let contract_name = contract_name.into();
         let op_name = op_name.into();
 
         let addr = self.contract_map.address(&contract_name)?;
 
+        let address: Address = addr
+            .parse()
+            .map_err(|e| ProcessError::CosmwasmError(CosmwasmError::AccountError(e)))?;
+
+        let span = OpSpan::enter(op_span!(
+            "migrate",
+            contract = %contract_name,
+            op_name = %op_name,
+            new_code_id
+        ));
+
         let res = tokio_block(async {
-            self.client
-                .wasm_migrate(
-                    MigrateRequest {
-                        address: addr.parse()?,
-                        new_code_id,
-                        msg,
-                    },
-                    key,
-                    &self.tx_options,
-                )
-                .await
-        })?;
+            let sender = key.to_addr(&self.chain_cfg.prefix).await?;
+            let req = MigrateRequest {
+                address: address.clone(),
+                new_code_id,
+                msg,
+            }
+            .to_proto(sender.clone())?;
 
-        self.contract_map
-            .register_contract(&contract_name, new_code_id);
+            let res = self.broadcast("migrate", vec![req], &sender, key).await?;
 
-        if let Some(p) = &mut self.gas_profiler {
-            p.instrument(
-                contract_name,
-                op_name,
-                CommandType::Migrate,
-                &res.res,
-                Location::caller(),
+            Ok::<_, CosmwasmError>(MigrateResponse { res })
+        });
+
+        self.record(
+            || {
+                Ok(TranscriptOp::Migrate {
+                    contract: contract_name.clone(),
+                    op_name: op_name.clone(),
+                    new_contract: self.contract_name_of(new_code_id),
+                    new_code_id,
+                    msg: serde_json::to_value(msg)?,
+                })
+            },
+            Some(key),
+            res.as_ref()
+                .map(|r| Some(&r.res))
+                .map_err(|e| e.to_string()),
+        );
+        let res = res?;
+        span.record_tx(&res.res);
+
+        self.contract_map
+            .register_contract(&contract_name, new_code_id);
+        self.save_state();
+
+        self.instrument(contract_name, op_name, CommandType::Migrate, &res.res, key);
+
+        Ok(res)
+    }
+
+    /// Sends `amounts` of native tokens to `to`.
+    ///
+    /// # Arguments
+    /// * `to` - Recipient address, or the name of a deployed smart contract in `contract_map`.
+    /// * `op_name` - Human readable operation name for profiling bookkeeping usage.
+    /// * `amounts` - Tokens to send.
+    /// * `key` - SigningKey, the name of a key in `Config::keys`, or `()` for the default key, used to sign the tx.
+    #[track_caller]
+    pub fn send<'k, S, K>(
+        &mut self,
+        to: S,
+        op_name: S,
+        amounts: Vec<Coin>,
+        key: K,
+    ) -> Result<SendResponse, ProcessError>
+    where
+        S: Into<String>,
+        K: Into<KeyRef<'k>>,
+    {
+        let key = &self.resolve_key(key.into())?;
+        let to = to.into();
+        let op_name = op_name.into();
+
+        let span = OpSpan::enter(op_span!("send", to = %to, op_name = %op_name));
+
+        let res = self.send_batch(
+            vec![SendReq {
+                to: to.clone(),
+                amounts: amounts.clone(),
+            }],
+            key,
+        );
+
+        self.record(
+            || {
+                Ok(TranscriptOp::Send {
+                    to: to.clone(),
+                    op_name: op_name.clone(),
+                    amounts,
+                })
+            },
+            Some(key),
+            res.as_ref()
+                .map(|r| Some(&r.res))
+                .map_err(|e| e.to_string()),
+        );
+        let res = res?;
+        span.record_tx(&res.res);
+
+        self.instrument(to, op_name, CommandType::Send, &res.res, key);
+
+        Ok(res)
+    }
+
+    /// Sends native tokens to multiple recipients in a single tx.
+    ///
+    /// # Arguments
+    /// * `op_name` - Human readable operation name for profiling bookkeeping usage.
+    /// * `reqs` - Bank sends to batch into a single tx.
+    /// * `key` - SigningKey, the name of a key in `Config::keys`, or `()` for the default key, used to sign the tx.
+    #[track_caller]
+    pub fn multi_send<'k, S, I, K>(
+        &mut self,
+        op_name: S,
+        reqs: I,
+        key: K,
+    ) -> Result<SendResponse, ProcessError>
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = SendReq>,
+        K: Into<KeyRef<'k>>,
+    {
+        let key = &self.resolve_key(key.into())?;
+        let op_name = op_name.into();
+
+        let reqs = reqs.into_iter().collect::<Vec<_>>();
+        let transcript_reqs = reqs
+            .iter()
+            .map(|r| TranscriptSend {
+                to: r.to.clone(),
+                amounts: r.amounts.clone(),
+            })
+            .collect();
+
+        let span = OpSpan::enter(op_span!(
+            "multi_send",
+            op_name = %op_name,
+            recipients = reqs.len()
+        ));
+
+        let res = self.send_batch(reqs, key);
+
+        self.record(
+            || {
+                Ok(TranscriptOp::MultiSend {
+                    op_name: op_name.clone(),
+                    reqs: transcript_reqs,
+                })
+            },
+            Some(key),
+            res.as_ref()
+                .map(|r| Some(&r.res))
+                .map_err(|e| e.to_string()),
+        );
+        let res = res?;
+        span.record_tx(&res.res);
+
+        self.instrument(
+            "multiple_recipients".to_string(),
+            op_name,
+            CommandType::Send,
+            &res.res,
+            key,
+        );
+
+        Ok(res)
+    }
+
+    fn send_batch<I>(&self, reqs: I, key: &SigningKey) -> Result<SendResponse, ProcessError>
+    where
+        I: IntoIterator<Item = SendReq>,
+    {
+        let from = tokio_block(async { key.to_addr(&self.chain_cfg.prefix).await })
+            .map_err(BankError::from)?;
+
+        let reqs = reqs
+            .into_iter()
+            .map(|r| {
+                Ok(SendRequest {
+                    from: from.clone(),
+                    to: self.resolve_address(&r.to)?,
+                    amounts: r.amounts,
+                })
+            })
+            .collect::<Result<Vec<_>, ProcessError>>()?;
+
+        let res = tokio_block(self.broadcast("send", reqs, &from, key)).map_err(BankError::from)?;
+
+        Ok(SendResponse { res })
+    }
+
+    /// Signs `msgs` and waits for the tx to be committed, retrying failures
+    /// according to [Self::retry_policy].
+    async fn broadcast<M: Msg + Serialize>(
+        &self,
+        op_name: &str,
+        msgs: Vec<M>,
+        sender: &Address,
+        key: &SigningKey,
+    ) -> Result<ChainTxResponse, TxError> {
+        self.retry_policy
+            .broadcast(op_name, &self.client, msgs, sender, key, &self.tx_options)
+            .await
+    }
+
+    /// Same as [Self::submit_execute()], sending native tokens to multiple recipients in a single tx.
+    ///
+    /// # Arguments
+    /// * `reqs` - Bank sends to batch into a single tx.
+    /// * `key` - SigningKey, the name of a key in `Config::keys`, or `()` for the default key, used to sign the tx.
+    pub fn submit_multi_send<'k, I, K>(
+        &mut self,
+        reqs: I,
+        key: K,
+    ) -> Result<AsyncChainTxResponse, ProcessError>
+    where
+        I: IntoIterator<Item = SendReq>,
+        K: Into<KeyRef<'k>>,
+    {
+        let key = &self.resolve_key(key.into())?;
+
+        let from = tokio_block(async { key.to_addr(&self.chain_cfg.prefix).await })
+            .map_err(BankError::from)?;
+
+        let msgs = reqs
+            .into_iter()
+            .map(|r| {
+                Ok(SendRequest {
+                    from: from.clone(),
+                    to: self.resolve_address(&r.to)?,
+                    amounts: r.amounts,
+                })
+            })
+            .collect::<Result<Vec<_>, ProcessError>>()?;
+
+        let sequences = self
+            .sequences
+            .as_mut()
+            .ok_or(ProcessError::SequenceManagerDisabled)?;
+
+        let res = tokio_block(async {
+            sequences
+                .submit(
+                    &self.client,
+                    &self.chain_cfg,
+                    msgs,
+                    &from,
+                    key,
+                    &self.tx_options,
+                )
+                .await
+        })
+        .map_err(BankError::from)?;
+
+        debug!("{:?}", res);
+
+        Ok(res)
+    }
+
+    /// Queries the `denom` balance held by `address`.
+    ///
+    /// # Arguments
+    /// * `address` - Account address, or the name of a deployed smart contract in `contract_map`.
+    /// * `denom` - Native token denom to query.
+    pub fn balance<S>(&self, address: S, denom: S) -> Result<Coin, ProcessError>
+    where
+        S: Into<String>,
+    {
+        let address = self.resolve_address(&address.into())?;
+        let denom: Denom = denom
+            .into()
+            .parse()
+            .map_err(|e| ProcessError::BankError(BankError::ChainError(e)))?;
+
+        let res = tokio_block(async { self.client.bank_query_balance(address, denom).await })?;
+
+        Ok(res.balance)
+    }
+
+    /// Queries every native token balance held by `address`.
+    ///
+    /// # Arguments
+    /// * `address` - Account address, or the name of a deployed smart contract in `contract_map`.
+    pub fn all_balances<S>(&self, address: S) -> Result<Vec<Coin>, ProcessError>
+    where
+        S: Into<String>,
+    {
+        let address = self.resolve_address(&address.into())?;
+
+        let balances = tokio_block(async {
+            let mut balances = vec![];
+            let mut pagination = None;
+
+            loop {
+                let res = self
+                    .client
+                    .bank_query_balances(address.clone(), pagination)
+                    .await?;
+
+                balances.extend(res.balances);
+
+                match res.next {
+                    Some(next) if !next.next_key.is_empty() => {
+                        pagination = Some(PaginationRequest {
+                            page: PageID::Key(next.next_key),
+                            limit: 100,
+                            reverse: false,
+                        });
+                    }
+                    _ => return Ok::<_, BankError>(balances),
+                }
+            }
+        })?;
+
+        Ok(balances)
+    }
+
+    /// Creates `n` accounts and funds them from `factory.funder` in a single multi-send tx.
+    ///
+    /// Unlike [Self::create_accounts()], leftover funds are never refunded automatically,
+    /// use [Self::refund_accounts()] during teardown.
+    ///
+    /// # Arguments
+    /// * `factory` - Configures how accounts are derived and funded.
+    /// * `n` - Number of accounts to create.
+    #[track_caller]
+    pub fn fund_accounts(
+        &mut self,
+        factory: &AccountFactory,
+        n: usize,
+    ) -> Result<Vec<TestAccount>, ProcessError> {
+        let accounts = tokio_block(async {
+            let mut accounts = Vec::with_capacity(n);
+
+            for i in 0..n {
+                let key = factory.key(i, &self.chain_cfg.derivation_path);
+                let address = key
+                    .to_addr(&self.chain_cfg.prefix)
+                    .await
+                    .map_err(BankError::from)?;
+
+                accounts.push(TestAccount { key, address });
+            }
+
+            Ok::<_, BankError>(accounts)
+        })?;
+
+        if !accounts.is_empty() && !factory.funds.is_empty() {
+            let reqs = accounts.iter().map(|a| SendReq {
+                to: a.address.to_string(),
+                amounts: factory.funds.clone(),
+            });
+
+            self.multi_send("fund_accounts", reqs, &factory.funder)?;
+        }
+
+        Ok(accounts)
+    }
+
+    /// Sends the leftover `factory.funds` denoms held by `accounts` back to `factory.funder`.
+    ///
+    /// Each account keeps enough of the chain fee denom to pay for its refund tx.
+    /// Every account is refunded even if some of them fail, the failures are returned together
+    /// as `ProcessError::RefundFailed`.
+    pub fn refund_accounts(
+        &self,
+        factory: &AccountFactory,
+        accounts: &[TestAccount],
+    ) -> Result<(), ProcessError> {
+        self.refund_plan(factory)?.run(&self.client, accounts)
+    }
+
+    fn refund_plan(&self, factory: &AccountFactory) -> Result<RefundPlan, ProcessError> {
+        let funder = tokio_block(async { factory.funder.to_addr(&self.chain_cfg.prefix).await })
+            .map_err(BankError::from)?;
+
+        Ok(RefundPlan::new(
+            &self.chain_cfg,
+            self.tx_options.clone(),
+            funder,
+            &factory.funds,
+        ))
+    }
+
+    /// Builds the `SigningKey` configured under `keys.<name>` in the `Config`.
+    pub fn signing_key(&self, name: &str) -> Result<SigningKey, KeyError> {
+        self.keys
+            .get(name)
+            .ok_or_else(|| KeyError::NotFound {
+                name: name.to_string(),
+            })?
+            .signing_key(name, &self.chain_cfg.derivation_path)
+    }
+
+    fn resolve_key<'k>(&self, key: KeyRef<'k>) -> Result<Cow<'k, SigningKey>, KeyError> {
+        match key {
+            KeyRef::Key(key) => Ok(Cow::Borrowed(key)),
+            KeyRef::Name(name) => Ok(Cow::Owned(self.signing_key(name)?)),
+            KeyRef::Default => self
+                .default_key
+                .clone()
+                .map(Cow::Owned)
+                .ok_or(KeyError::NoDefaultKey),
+        }
+    }
+
+    /// Renders the instantiate label template
+    fn label(&self, contract_name: &str, op_name: &str) -> String {
+        self.label
+            .replace("{contract_name}", contract_name)
+            .replace("{op_name}", op_name)
+    }
+
+    /// Writes the contract map to the configured state file.
+    ///
+    /// Only called after a tx has been committed, so failures are logged instead of
+    /// hiding the code id or address from the caller.
+    fn save_state(&self) {
+        if let Some(path) = &self.state_file {
+            if let Err(e) = self.contract_map.save(path) {
+                warn!(path = %path.display(), "failed to save state file: {e}");
+            }
+        }
+    }
+
+    /// Appends the operation built by `op` to the configured transcript.
+    ///
+    /// Failures are logged, since recording must not change the result of the operation.
+    fn record<F>(
+        &self,
+        op: F,
+        key: Option<&SigningKey>,
+        result: Result<Option<&ChainTxResponse>, String>,
+    ) where
+        F: FnOnce() -> Result<TranscriptOp, serde_json::Error>,
+    {
+        let Some(path) = &self.transcript else {
+            return;
+        };
+
+        let res = op().map_err(std::io::Error::from).and_then(|op| {
+            let entry = TranscriptEntry::new(
+                op,
+                key.and_then(|k| self.signer_address(k)),
+                key.map(|k| k.name.clone()),
+                result.map(|res| res.map(|res| TxRecord::new(res, self.tx_fee(res)))),
             );
+
+            transcript::append(path, &entry)
+        });
+
+        if let Err(e) = res {
+            warn!(path = %path.display(), "failed to record transcript entry: {e}");
         }
+    }
 
-        debug!("{:?}", res.res);
+    fn signer_address(&self, key: &SigningKey) -> Option<String> {
+        tokio_block(async { key.to_addr(&self.chain_cfg.prefix).await })
+            .ok()
+            .map(|a| a.to_string())
+    }
 
-        Ok(res)
+    /// Name of the stored contract with `code_id`, if any
+    fn contract_name_of(&self, code_id: u64) -> Option<String> {
+        self.contract_map
+            .deploy_info()
+            .iter()
+            .filter(|(_, info)| info.code_id == Some(code_id))
+            .map(|(name, _)| name.clone())
+            .min()
+    }
+
+    /// Returns the address of the deployed contract `name_or_addr`, or parses it as an address
+    /// when `name_or_addr` is not a contract in `contract_map`.
+    fn resolve_address(&self, name_or_addr: &str) -> Result<Address, ProcessError> {
+        let addr = match self.contract_map.address(name_or_addr) {
+            Ok(addr) => addr,
+            Err(ContractMapError::NotStored { .. }) => name_or_addr.to_string(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(addr.parse().map_err(BankError::from)?)
     }
 
     /// Blocks the current thread until `n` blocks have been processed.
@@ -454,175 +1374,1654 @@ impl<C: CosmosClient> CosmOrc<C> {
         timeout: T,
         is_first_block: bool,
     ) -> Result<(), PollBlockError> {
+        self.poll_for_n_blocks_with_policy(n, timeout, is_first_block, &self.poll_policy)
+    }
+
+    /// Same as [Self::poll_for_n_blocks()], but uses `policy` instead of the configured `poll_policy`.
+    pub fn poll_for_n_blocks_with_policy<T: Into<Duration> + Send>(
+        &self,
+        n: u64,
+        timeout: T,
+        is_first_block: bool,
+        policy: &PollPolicy,
+    ) -> Result<(), PollBlockError> {
+        let _span = OpSpan::enter(info_span!(
+            "poll_for_n_blocks",
+            n,
+            duration_ms = field::Empty
+        ));
+
+        let timeout = timeout.into();
+        let start = Instant::now();
+
+        if policy.subscribe {
+            if is_first_block {
+                tokio_block(async {
+                    _timeout(timeout, self.wait_for_first_block(&mut policy.poller())).await
+                })??;
+            }
+
+            return self.wait_for_n_new_blocks(n, timeout.saturating_sub(start.elapsed()));
+        }
+
         tokio_block(async {
-            _timeout(timeout.into(), async {
+            _timeout(timeout, async {
+                let mut poller = policy.poller();
+
                 if is_first_block {
-                    while let Err(e) = self.client.tendermint_query_latest_block().await {
-                        if !matches!(e, TendermintError::ChainError { .. }) {
-                            return Err(PollBlockError::TendermintError(e));
+                    self.wait_for_first_block(&mut poller).await?;
+                }
+
+                let mut target_height = None;
+
+                loop {
+                    match self.latest_block_height().await {
+                        Ok(curr_height) => {
+                            let target_height = *target_height.get_or_insert(curr_height + n);
+                            if curr_height >= target_height {
+                                return Ok::<(), PollBlockError>(());
+                            }
+                            poller.reset_errors();
+                        }
+                        Err(e) => poller.record_error(e)?,
+                    }
+
+                    time::sleep(poller.next_delay()).await;
+                }
+            })
+            .await
+        })??;
+
+        Ok(())
+    }
+
+    async fn wait_for_first_block(&self, poller: &mut Poller<'_>) -> Result<(), PollBlockError> {
+        while let Err(e) = self.client.tendermint_query_latest_block().await {
+            if !matches!(e, TendermintError::ChainError { .. }) {
+                return Err(PollBlockError::TendermintError(e));
+            }
+            time::sleep(poller.next_delay()).await;
+        }
+
+        Ok(())
+    }
+
+    fn wait_for_n_new_blocks(&self, n: u64, timeout: Duration) -> Result<(), PollBlockError> {
+        let deadline = Instant::now() + timeout;
+        let mut stream = self.event_stream(EventType::NewBlock.into())?;
+
+        let mut blocks = 0;
+        while blocks < n {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            match stream.next_event_timeout(remaining) {
+                Ok(Some(ChainEvent::NewBlock { .. })) => blocks += 1,
+                Ok(Some(ChainEvent::Tx(..))) => {}
+                Ok(None) => return Err(SubscribeError::Closed.into()),
+                Err(SubscribeError::Timeout(e)) => return Err(PollBlockError::Timeout(e)),
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn event_stream(&self, query: Query) -> Result<EventStream, SubscribeError> {
+        let rpc_endpoint = self
+            .chain_cfg
+            .rpc_endpoint
+            .as_ref()
+            .ok_or(SubscribeError::MissingRpcEndpoint)?;
+
+        EventStream::connect(rpc_endpoint, query)
+    }
+
+    /// Blocks the current thread until `n` block header seconds have passed.
+    ///
+    /// Local system time can differ from block header time, so this can be used to reliably wait n block seconds
+    /// before some action can be taken in a smart contract.
+    ///
+    /// # Arguments
+    /// * `n` - Wait for this number of block header seconds to pass.
+    /// * `timeout` - Throws `PollBlockError` once `timeout` has elapsed.
+    pub fn poll_for_n_secs<T: Into<Duration> + Send>(
+        &self,
+        n: u64,
+        timeout: T,
+    ) -> Result<(), PollBlockError> {
+        self.poll_for_n_secs_with_policy(n, timeout, &self.poll_policy)
+    }
+
+    /// Same as [Self::poll_for_n_secs()], but uses `policy` instead of the configured `poll_policy`.
+    pub fn poll_for_n_secs_with_policy<T: Into<Duration> + Send>(
+        &self,
+        n: u64,
+        timeout: T,
+        policy: &PollPolicy,
+    ) -> Result<(), PollBlockError> {
+        let _span = OpSpan::enter(info_span!("poll_for_n_secs", n, duration_ms = field::Empty));
+
+        tokio_block(async {
+            _timeout(timeout.into(), async {
+                let mut poller = policy.poller();
+                let mut target_time = None;
+
+                loop {
+                    match self.latest_block_time_secs().await {
+                        Ok(curr_time_secs) => {
+                            let target_time = *target_time.get_or_insert(curr_time_secs + n);
+                            if curr_time_secs >= target_time {
+                                return Ok::<(), PollBlockError>(());
+                            }
+                            poller.reset_errors();
+                        }
+                        Err(e) => poller.record_error(e)?,
+                    }
+
+                    time::sleep(poller.next_delay()).await;
+                }
+            })
+            .await
+        })??;
+
+        Ok(())
+    }
+
+    async fn latest_block_height(&self) -> Result<u64, PollBlockError> {
+        let header = self
+            .client
+            .tendermint_query_latest_block()
+            .await?
+            .block
+            .header
+            .ok_or(PollBlockError::MissingBlockHeader)?;
+
+        Ok(header.height as u64)
+    }
+
+    async fn latest_block_time_secs(&self) -> Result<u64, PollBlockError> {
+        let time = self
+            .client
+            .tendermint_query_latest_block()
+            .await?
+            .block
+            .header
+            .ok_or(PollBlockError::MissingBlockHeader)?
+            .time
+            .ok_or(PollBlockError::MissingBlockTime)?;
+
+        Ok(time.seconds as u64)
+    }
+
+    /// Blocks the current thread until the smart contract query `msg` returns a response satisfying `predicate`.
+    ///
+    /// Useful for waiting on contract state that is changed by something outside of the current test,
+    /// for example a vesting schedule unlocking or a relayer pushing an oracle price update.
+    ///
+    /// # Arguments
+    /// * `contract_name` - Deployed smart contract name for the corresponding `msg`.
+    /// * `msg` - QueryMsg that `contract_name` supports.
+    /// * `predicate` - Returns true once the query response is in the desired state.
+    /// * `interval` - Time to wait in between each query.
+    /// * `timeout` - Throws `PollQueryError` once `timeout` has elapsed.
+    ///
+    /// # Errors
+    /// * If `contract_name` has not been instantiated via [Self::instantiate()]
+    ///   `cosm_orc::orchestrator::error::ContractMapError::NotDeployed` is thrown.
+    pub fn poll_until<S, T, F, D>(
+        &self,
+        contract_name: S,
+        msg: &T,
+        mut predicate: F,
+        interval: D,
+        timeout: D,
+    ) -> Result<QueryResponse, PollQueryError>
+    where
+        S: Into<String>,
+        T: Serialize,
+        F: FnMut(&QueryResponse) -> bool,
+        D: Into<Duration> + Send,
+    {
+        let contract_name = contract_name.into();
+        let interval = interval.into();
+
+        let _span = OpSpan::enter(info_span!(
+            "poll_until",
+            contract = %contract_name,
+            duration_ms = field::Empty
+        ));
+
+        let addr: Address = self
+            .contract_map
+            .address(&contract_name)
+            .map_err(ProcessError::from)?
+            .parse()
+            .map_err(|e| ProcessError::CosmwasmError(CosmwasmError::AccountError(e)))?;
+
+        let res = tokio_block(async {
+            _timeout(timeout.into(), async {
+                loop {
+                    let res = self
+                        .client
+                        .wasm_query(addr.clone(), msg)
+                        .await
+                        .map_err(ProcessError::from)?;
+
+                    if predicate(&res) {
+                        return Ok::<QueryResponse, PollQueryError>(res);
+                    }
+
+                    time::sleep(interval).await;
+                }
+            })
+            .await
+        })??;
+
+        Ok(res)
+    }
+
+    /// Fires txs executing `contract_name` at the `load.rate` for `load.duration`.
+    ///
+    /// Every tx is signed by the next idle key of `load.keys`, with the msg returned by
+    /// `msg_fn(n, key)` for the n-th tx. Tx failures are reported in the returned [LoadReport]
+    /// instead of stopping the load test. Successful txs are recorded by the gas profiler.
+    ///
+    /// # Arguments
+    /// * `contract_name` - Deployed smart contract name for the generated msgs.
+    /// * `op_name` - Human readable operation name for profiling bookkeeping usage.
+    /// * `load` - Keys, rate and duration of the load test.
+    /// * `msg_fn` - Generates the ExecuteMsg of the n-th tx.
+    ///
+    /// # Errors
+    /// * If `contract_name` has not been instantiated via [Self::instantiate()]
+    ///   `cosm_orc::orchestrator::error::ContractMapError::NotDeployed` is thrown.
+    #[track_caller]
+    pub fn load_test<S, T, F>(
+        &mut self,
+        contract_name: S,
+        op_name: S,
+        load: &LoadTest,
+        mut msg_fn: F,
+    ) -> Result<LoadReport, LoadTestError>
+    where
+        S: Into<String>,
+        T: Serialize,
+        F: FnMut(u64, &SigningKey) -> T,
+    {
+        load.validate()?;
+        let interval = load.interval()?;
+
+        let contract_name = contract_name.into();
+        let op_name = op_name.into();
+        let address: Address = self
+            .contract_map
+            .address(&contract_name)
+            .map_err(ProcessError::from)?
+            .parse()
+            .map_err(|e| ProcessError::CosmwasmError(CosmwasmError::AccountError(e)))?;
+
+        let signers = match self.gas_profiler {
+            Some(_) => load.keys.iter().map(|k| self.signer_address(k)).collect(),
+            None => vec![None; load.keys.len()],
+        };
+
+        let caller = Location::caller();
+        let client = &self.client;
+        let chain_cfg = &self.chain_cfg;
+        let tx_options = &self.tx_options;
+        let gas_profiler = &mut self.gas_profiler;
+
+        let mut record =
+            |stats: &mut LoadStats, i: usize, latency, res: Result<ExecResponse, _>| match res {
+                Ok(res) => {
+                    if let Some(p) = gas_profiler {
+                        p.instrument(
+                            contract_name.clone(),
+                            op_name.clone(),
+                            CommandType::Execute,
+                            &res.res,
+                            &fee_paid(chain_cfg, tx_options, res.res.gas_wanted),
+                            signers[i].as_deref(),
+                            caller,
+                        );
+                    }
+                    stats.success(latency, &res.res);
+                }
+                Err(e) => stats.failure(&ProcessError::CosmwasmError(e)),
+            };
+
+        let report = tokio_block(async {
+            let start = time::Instant::now();
+            let deadline = start + load.duration;
+
+            let mut stats = LoadStats::default();
+            let mut in_flight = FuturesUnordered::new();
+            let mut busy = vec![false; load.keys.len()];
+            let mut next_key = 0;
+            let mut next_tick = start;
+
+            while time::Instant::now() < deadline {
+                if time::Instant::now() >= next_tick {
+                    next_tick += interval;
+
+                    let idle = (0..busy.len())
+                        .map(|i| (next_key + i) % busy.len())
+                        .find(|i| !busy[*i]);
+
+                    let Some(i) = idle else {
+                        stats.skipped += 1;
+                        continue;
+                    };
+
+                    let key = &load.keys[i];
+                    let req = ExecRequest {
+                        address: address.clone(),
+                        msg: msg_fn(stats.sent, key),
+                        funds: vec![],
+                    };
+
+                    busy[i] = true;
+                    next_key = i + 1;
+                    stats.sent += 1;
+
+                    in_flight.push(async move {
+                        let sent = time::Instant::now();
+                        let res = client.wasm_execute(req, key, tx_options).await;
+                        (i, sent.elapsed(), res)
+                    });
+                    continue;
+                }
+
+                let sleep = Box::pin(time::sleep_until(next_tick.min(deadline)));
+                if in_flight.is_empty() {
+                    sleep.await;
+                    continue;
+                }
+
+                if let Either::Right((Some((i, latency, res)), _)) =
+                    future::select(sleep, in_flight.next()).await
+                {
+                    busy[i] = false;
+                    record(&mut stats, i, latency, res);
+                }
+            }
+
+            while let Some((i, latency, res)) = in_flight.next().await {
+                record(&mut stats, i, latency, res);
+            }
+
+            stats.finish(start.elapsed())
+        });
+
+        info!(
+            "load test of {contract_name}: {} txs sent, {:.2} tps",
+            report.sent, report.tps
+        );
+
+        Ok(report)
+    }
+
+    /// Replays the operations recorded in the transcript at `path` against the configured chain.
+    ///
+    /// Contracts are resolved by name through `contract_map`, so a scenario recorded on one chain
+    /// can be replayed on another one or on a fresh local node. Each operation is signed with the
+    /// key of the same name in `Config::keys` if there is one, and with `key` otherwise.
+    ///
+    /// Failed operations don't stop the replay, their errors are returned in the replayed entries.
+    ///
+    /// # Arguments
+    /// * `path` - Transcript recorded with [Self::set_transcript()].
+    /// * `key` - SigningKey, or the name of a key in `Config::keys`, used for unknown signers.
+    #[track_caller]
+    pub fn replay_transcript<'k, P, K>(
+        &mut self,
+        path: P,
+        key: K,
+    ) -> Result<Vec<TranscriptEntry>, TranscriptError>
+    where
+        P: AsRef<Path>,
+        K: Into<KeyRef<'k>>,
+    {
+        let fallback = self.resolve_key(key.into())?.into_owned();
+        let mut replayed = vec![];
+
+        for entry in transcript::read(path)? {
+            let key = match entry.key.as_deref() {
+                Some(name) if self.keys.contains_key(name) => self.signing_key(name)?,
+                _ => fallback.clone(),
+            };
+
+            let result = self.replay_op(&entry.op, &key);
+
+            let (signer, key) = match entry.op {
+                TranscriptOp::Query { .. } => (None, None),
+                _ => (self.signer_address(&key), Some(key.name)),
+            };
+
+            replayed.push(TranscriptEntry::new(
+                entry.op,
+                signer,
+                key,
+                result.map(|res| res.map(|res| TxRecord::new(&res, self.tx_fee(&res)))),
+            ));
+        }
+
+        Ok(replayed)
+    }
+
+    #[track_caller]
+    fn replay_op(
+        &mut self,
+        op: &TranscriptOp,
+        key: &SigningKey,
+    ) -> Result<Option<ChainTxResponse>, String> {
+        let res = match op {
+            TranscriptOp::Store {
+                contract,
+                wasm_path,
+                instantiate_perms,
+            } => {
+                self.store_contract(contract, wasm_path, key, instantiate_perms.clone())
+                    .map_err(|e| e.to_string())?
+                    .res
+            }
+            TranscriptOp::Instantiate {
+                contract,
+                op_name,
+                msg,
+                admin,
+                funds,
+            } => {
+                let admin = admin
+                    .as_ref()
+                    .map(|a| a.parse())
+                    .transpose()
+                    .map_err(|e: AccountError| e.to_string())?;
+
+                self.instantiate(
+                    contract.as_str(),
+                    op_name.as_str(),
+                    msg,
+                    key,
+                    admin,
+                    funds.clone(),
+                )
+                .map_err(|e| e.to_string())?
+                .res
+            }
+            TranscriptOp::Execute {
+                contract,
+                op_name,
+                msg,
+                funds,
+            } => {
+                self.execute(contract.as_str(), op_name.as_str(), msg, key, funds.clone())
+                    .map_err(|e| e.to_string())?
+                    .res
+            }
+            TranscriptOp::ExecuteBatch { op_name, reqs } => {
+                let reqs = reqs.iter().map(|r| ExecReq {
+                    contract_name: r.contract.clone(),
+                    msg: Box::new(r.msg.clone()),
+                    funds: r.funds.clone(),
+                });
+
+                self.execute_batch(op_name.as_str(), reqs, key)
+                    .map_err(|e| e.to_string())?
+                    .res
+            }
+            TranscriptOp::Migrate {
+                contract,
+                op_name,
+                new_contract,
+                new_code_id,
+                msg,
+            } => {
+                // code ids differ between chains, so the stored contract is looked up by name
+                let new_code_id = new_contract
+                    .as_ref()
+                    .and_then(|name| self.contract_map.code_id(name).ok())
+                    .unwrap_or(*new_code_id);
+
+                self.migrate(contract.as_str(), new_code_id, op_name.as_str(), msg, key)
+                    .map_err(|e| e.to_string())?
+                    .res
+            }
+            TranscriptOp::Query { contract, msg } => {
+                self.query(contract.as_str(), msg)
+                    .map_err(|e| e.to_string())?;
+
+                return Ok(None);
+            }
+            TranscriptOp::Send {
+                to,
+                op_name,
+                amounts,
+            } => {
+                self.send(to.as_str(), op_name.as_str(), amounts.clone(), key)
+                    .map_err(|e| e.to_string())?
+                    .res
+            }
+            TranscriptOp::MultiSend { op_name, reqs } => {
+                let reqs = reqs.iter().map(|r| SendReq {
+                    to: r.to.clone(),
+                    amounts: r.amounts.clone(),
+                });
+
+                self.multi_send(op_name.as_str(), reqs, key)
+                    .map_err(|e| e.to_string())?
+                    .res
+            }
+        };
+
+        Ok(Some(res))
+    }
+
+    /// Get gas usage report
+    pub fn gas_profiler_report(&self) -> Option<&Report> {
+        self.gas_profiler.as_ref().map(|p| p.report())
+    }
+
+    /// Get the total fees paid per contract and per signer.
+    ///
+    /// Fees of failed txs are not included, see [FeeReport].
+    pub fn fee_report(&self) -> Option<&FeeReport> {
+        self.gas_profiler.as_ref().map(|p| p.fee_report())
+    }
+
+    /// Fee paid by the committed tx `res`, in the configured `denom` unless `TxOptions::fee` is set
+    pub fn tx_fee(&self, res: &ChainTxResponse) -> Vec<Coin> {
+        fee_paid(&self.chain_cfg, &self.tx_options, res.gas_wanted)
+    }
+
+    /// Records `res` in the gas profiler, if enabled
+    #[track_caller]
+    fn instrument(
+        &mut self,
+        contract: String,
+        op_name: String,
+        op_type: CommandType,
+        res: &ChainTxResponse,
+        key: &SigningKey,
+    ) {
+        if self.gas_profiler.is_none() {
+            return;
+        }
+
+        let fee = self.tx_fee(res);
+        let signer = self.signer_address(key);
+
+        if let Some(p) = &mut self.gas_profiler {
+            p.instrument(
+                contract,
+                op_name,
+                op_type,
+                res,
+                &fee,
+                signer.as_deref(),
+                Location::caller(),
+            );
+        }
+    }
+}
+
+impl<C: CosmosClient + Clone> CosmOrc<C> {
+    /// Creates `n` accounts and funds them from `factory.funder` in a single multi-send tx.
+    ///
+    /// If `factory.refund` is set, leftover funds are returned to the funder
+    /// once the returned [TestAccounts] are dropped or torn down.
+    ///
+    /// # Arguments
+    /// * `factory` - Configures how accounts are derived, funded and refunded.
+    /// * `n` - Number of accounts to create.
+    #[track_caller]
+    pub fn create_accounts(
+        &mut self,
+        factory: &AccountFactory,
+        n: usize,
+    ) -> Result<TestAccounts<C>, ProcessError> {
+        let accounts = self.fund_accounts(factory, n)?;
+
+        let refund = if factory.refund {
+            Some(Refund {
+                client: self.client.clone(),
+                plan: self.refund_plan(factory)?,
+            })
+        } else {
+            None
+        };
+
+        Ok(TestAccounts::new(accounts, refund))
+    }
+}
+
+pub fn tokio_block<F: Future>(f: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokio_block, CosmOrc, DEFAULT_LABEL};
+    use crate::config::cfg::Config;
+    use crate::config::error::{ConfigError, KeyError};
+    use crate::orchestrator::accounts::{AccountFactory, TestAccount};
+    use crate::orchestrator::deploy::DeployInfo;
+    use crate::orchestrator::error::InitError;
+    use crate::orchestrator::failover::{ActiveEndpoint, FailoverClient};
+    use crate::orchestrator::gas_profiler::GasProfiler;
+    use crate::orchestrator::load::LoadTest;
+    use crate::orchestrator::poll::PollPolicy;
+    use crate::orchestrator::retry::{RetryPolicy, RetryableError};
+    use crate::orchestrator::sequence::SequenceManager;
+    use crate::orchestrator::store::{ArtifactFilter, StorePolicy};
+    use crate::orchestrator::transcript::{self, TranscriptOp};
+    use crate::orchestrator::{
+        deploy::ContractMap,
+        error::{
+            ContractMapError, LoadTestError, PollBlockError, PollQueryError, ProcessError,
+            StoreError, SubscribeError,
+        },
+        Coin, SendReq,
+    };
+    use assert_matches::assert_matches;
+    use cosm_tome::chain::error::ChainError;
+    use cosm_tome::chain::fee::GasInfo;
+    use cosm_tome::chain::request::TxOptions;
+    use cosm_tome::chain::response::{
+        AsyncChainTxResponse, ChainResponse, ChainTxResponse, Code, Event, Tag,
+    };
+    use cosm_tome::clients::client::{CosmTome, CosmosClient, MockCosmosClient};
+    use cosm_tome::config::cfg::ChainConfig;
+    use cosm_tome::modules::auth::error::AccountError;
+    use cosm_tome::modules::bank::error::BankError;
+    use cosm_tome::modules::cosmwasm::error::CosmwasmError;
+    use cosm_tome::modules::tx::error::TxError;
+    use cosm_tome::signing_key::key::{Key, SigningKey};
+    use cosmos_sdk_proto::cosmos::auth::v1beta1::{
+        BaseAccount, QueryAccountRequest, QueryAccountResponse,
+    };
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::{QueryBalanceRequest, QueryBalanceResponse};
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
+    use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::{
+        GetLatestBlockRequest, GetLatestBlockResponse,
+    };
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::{GetTxRequest, GetTxResponse};
+    use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+        QuerySmartContractStateRequest, QuerySmartContractStateResponse,
+    };
+    use cosmos_sdk_proto::tendermint::types::{Block, BlockId, Header};
+    use cosmos_sdk_proto::traits::MessageExt;
+    use serde::Serialize;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::time::Duration;
+    use std::vec;
+
+    #[derive(Serialize)]
+    pub struct TestMsg {}
+
+    pub fn test_cfg() -> ChainConfig {
+        ChainConfig {
+            denom: "utest".to_string(),
+            prefix: "test".to_string(),
+            chain_id: "test-1".to_string(),
+            rpc_endpoint: None,
+            grpc_endpoint: Some("localhost:12690".to_string()),
+            derivation_path: "m/44'/118'/0'/0/0".to_string(),
+            gas_price: 0.1,
+            gas_adjustment: 1.5,
+        }
+    }
+
+    /// `CosmOrc` backed by `client`, using `test_cfg()` and the default options
+    fn test_cosm_orc<C: CosmosClient>(client: C) -> CosmOrc<C> {
+        let cfg = test_cfg();
+
+        CosmOrc {
+            contract_map: ContractMap::new(HashMap::new()),
+            client: CosmTome::new(cfg.clone(), client),
+            poll_policy: PollPolicy::default(),
+            retry_policy: RetryPolicy::default(),
+            store_policy: StorePolicy::default(),
+            chain_cfg: cfg,
+            keys: HashMap::new(),
+            gas_profiler: None,
+            tx_options: TxOptions::default(),
+            default_key: None,
+            label: DEFAULT_LABEL.to_string(),
+            state_file: None,
+            sequences: None,
+            endpoint: None,
+            transcript: None,
+        }
+    }
+
+    /// Mocks the account query and tx simulation done before every broadcast
+    fn mock_tx_setup(mock_client: &mut MockCosmosClient, times: usize) {
+        mock_client
+            .expect_query::<QueryAccountRequest, QueryAccountResponse>()
+            .times(times)
+            .returning(move |_, t: &str| {
+                Ok(QueryAccountResponse {
+                    account: Some(cosmos_sdk_proto::Any {
+                        type_url: t.to_owned(),
+                        value: BaseAccount {
+                            address: "juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg".to_string(),
+                            pub_key: None,
+                            account_number: 1221,
+                            sequence: 1,
+                        }
+                        .to_bytes()
+                        .unwrap(),
+                    }),
+                })
+            });
+
+        mock_client
+            .expect_simulate_tx()
+            .times(times)
+            .returning(|_| {
+                Ok(GasInfo {
+                    gas_wanted: 200u16.into(),
+                    gas_used: 100u16.into(),
+                })
+            });
+    }
+
+    #[test]
+    fn instantiate_not_stored() {
+        let cfg = test_cfg();
+        let code_ids = HashMap::new();
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(code_ids),
+            ..test_cosm_orc(MockCosmosClient::new())
+        };
+
+        let res = cosm_orc.instantiate("cw_not_stored", "i_test", &TestMsg {}, &key, None, vec![]);
+
+        assert_matches!(
+            res.unwrap_err(),
+            ProcessError::ContractMapError(e) if e == ContractMapError::NotStored{name: "cw_not_stored".to_string()}
+        );
+
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_not_stored").unwrap_err(),
+            ContractMapError::NotStored {
+                name: "cw_not_stored".to_string()
+            }
+        );
+
+        assert_eq!(cosm_orc.gas_profiler_report(), None);
+    }
+
+    #[test]
+    fn instantiate_cosmossdk_error() {
+        let cfg = test_cfg();
+        let code_ids = HashMap::from([(
+            "cw_test".to_string(),
+            DeployInfo {
+                code_id: Some(1337),
+                address: None,
+            },
+        )]);
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let msg = &TestMsg {};
+
+        let mut mock_client = MockCosmosClient::new();
+
+        mock_client
+            .expect_query::<QueryAccountRequest, QueryAccountResponse>()
+            .times(1)
+            .returning(move |_, _| {
+                Err(ChainError::CosmosSdk {
+                    res: ChainResponse {
+                        code: Code::Err(1),
+                        data: None,
+                        log: "error".to_string(),
+                    },
+                })
+            });
+
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(code_ids),
+            ..test_cosm_orc(mock_client)
+        };
+
+        let res = cosm_orc.instantiate("cw_test", "i_test", msg, &key, None, vec![]);
+
+        assert_matches!(
+            res.unwrap_err(),
+            ProcessError::CosmwasmError(CosmwasmError::TxError(TxError::AccountError(
+                AccountError::ChainError(ChainError::CosmosSdk { .. })
+            )))
+        );
+
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_test").unwrap_err(),
+            ContractMapError::NotDeployed {
+                name: "cw_test".to_string()
+            }
+        );
+
+        assert_eq!(cosm_orc.gas_profiler_report(), None);
+    }
+
+    #[test]
+    fn instantiate() {
+        let cfg = test_cfg();
+        let code_ids = HashMap::from([(
+            "cw_test".to_string(),
+            DeployInfo {
+                code_id: Some(1337),
+                address: None,
+            },
+        )]);
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let msg = &TestMsg {};
+
+        let mut mock_client = MockCosmosClient::new();
+
+        mock_client
+            .expect_query::<QueryAccountRequest, QueryAccountResponse>()
+            .times(1)
+            .returning(move |_, t: &str| {
+                Ok(QueryAccountResponse {
+                    account: Some(cosmos_sdk_proto::Any {
+                        type_url: t.to_owned(),
+                        value: BaseAccount {
+                            address: "juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg".to_string(),
+                            pub_key: None,
+                            account_number: 1221,
+                            sequence: 1,
+                        }
+                        .to_bytes()
+                        .unwrap(),
+                    }),
+                })
+            });
+
+        mock_client.expect_simulate_tx().times(1).returning(|_| {
+            Ok(GasInfo {
+                gas_wanted: 200u16.into(),
+                gas_used: 100u16.into(),
+            })
+        });
+
+        mock_client
+            .expect_broadcast_tx_block()
+            .times(1)
+            .returning(|_| {
+                Ok(ChainTxResponse {
+                    res: ChainResponse {
+                        code: Code::Ok,
+                        data: None,
+                        log: "log log log".to_string(),
+                    },
+                    events: vec![Event {
+                        type_str: "instantiate".to_string(),
+                        attributes: vec![Tag {
+                            key: "_contract_address".to_string(),
+                            value: "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string(),
+                        }],
+                    }],
+                    gas_wanted: 101,
+                    gas_used: 100,
+                    tx_hash: "TX_HASH_0".to_string(),
+                    height: 1234,
+                })
+            });
+
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(code_ids),
+            ..test_cosm_orc(mock_client)
+        };
+
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_test").unwrap_err(),
+            ContractMapError::NotDeployed {
+                name: "cw_test".to_string()
+            }
+        );
+
+        let res = cosm_orc
+            .instantiate("cw_test", "i_test", msg, &key, None, vec![])
+            .unwrap()
+            .res;
+
+        assert_eq!(res.res.code, Code::Ok);
+        assert_eq!(res.res.data, None);
+        assert_eq!(res.res.log, "log log log".to_string());
+        assert_eq!(res.height, 1234);
+        assert_eq!(res.tx_hash, "TX_HASH_0".to_string());
+        assert_eq!(res.gas_used, 100);
+        assert_eq!(res.gas_wanted, 101);
+
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_test").unwrap(),
+            "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()
+        );
+
+        assert_eq!(cosm_orc.gas_profiler_report(), None);
+    }
+
+    #[test]
+    fn instantiate_ignores_state_file_errors() {
+        let cfg = test_cfg();
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let mut mock_client = MockCosmosClient::new();
+        mock_tx_setup(&mut mock_client, 1);
+        mock_client
+            .expect_broadcast_tx_block()
+            .times(1)
+            .returning(|_| {
+                Ok(ChainTxResponse {
+                    res: ChainResponse {
+                        code: Code::Ok,
+                        data: None,
+                        log: "".to_string(),
+                    },
+                    events: vec![Event {
+                        type_str: "instantiate".to_string(),
+                        attributes: vec![Tag {
+                            key: "_contract_address".to_string(),
+                            value: "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string(),
+                        }],
+                    }],
+                    gas_wanted: 101,
+                    gas_used: 100,
+                    tx_hash: "TX_HASH_0".to_string(),
+                    height: 1234,
+                })
+            });
+
+        // a directory can't be written as a state file
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(HashMap::from([(
+                "cw_test".to_string(),
+                DeployInfo {
+                    code_id: Some(1337),
+                    address: None,
+                },
+            )])),
+            state_file: Some(std::env::temp_dir()),
+            ..test_cosm_orc(mock_client)
+        };
+
+        let res = cosm_orc
+            .instantiate("cw_test", "i_test", &TestMsg {}, &key, None, vec![])
+            .unwrap();
+
+        assert_eq!(
+            res.address.to_string(),
+            "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj"
+        );
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_test").unwrap(),
+            "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj"
+        );
+    }
+
+    #[test]
+    fn instantiate_with_profiler() {
+        let cfg = test_cfg();
+        let code_ids = HashMap::from([(
+            "cw_test".to_string(),
+            DeployInfo {
+                code_id: Some(1337),
+                address: None,
+            },
+        )]);
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let msg = &TestMsg {};
+
+        let mut mock_client = MockCosmosClient::new();
+
+        mock_client
+            .expect_query::<QueryAccountRequest, QueryAccountResponse>()
+            .times(1)
+            .returning(move |_, t: &str| {
+                Ok(QueryAccountResponse {
+                    account: Some(cosmos_sdk_proto::Any {
+                        type_url: t.to_owned(),
+                        value: BaseAccount {
+                            address: "juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg".to_string(),
+                            pub_key: None,
+                            account_number: 1221,
+                            sequence: 1,
+                        }
+                        .to_bytes()
+                        .unwrap(),
+                    }),
+                })
+            });
+
+        mock_client.expect_simulate_tx().times(1).returning(|_| {
+            Ok(GasInfo {
+                gas_wanted: 200u16.into(),
+                gas_used: 100u16.into(),
+            })
+        });
+
+        mock_client
+            .expect_broadcast_tx_block()
+            .times(1)
+            .returning(|_| {
+                Ok(ChainTxResponse {
+                    res: ChainResponse {
+                        code: Code::Ok,
+                        data: None,
+                        log: "".to_string(),
+                    },
+                    events: vec![Event {
+                        type_str: "instantiate".to_string(),
+                        attributes: vec![Tag {
+                            key: "_contract_address".to_string(),
+                            value: "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string(),
+                        }],
+                    }],
+                    gas_wanted: 101,
+                    gas_used: 100,
+                    tx_hash: "TX_HASH_0".to_string(),
+                    height: 1234,
+                })
+            });
+
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(code_ids),
+            gas_profiler: Some(GasProfiler::new()),
+            ..test_cosm_orc(mock_client)
+        };
+        cosm_orc.set_endpoint(ActiveEndpoint::new(vec![
+            "http://localhost:9090/".to_string()
+        ]));
+
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_test").unwrap_err(),
+            ContractMapError::NotDeployed {
+                name: "cw_test".to_string()
+            }
+        );
+
+        let res = cosm_orc
+            .instantiate("cw_test", "i_test", msg, &key, None, vec![])
+            .unwrap()
+            .res;
+
+        assert_eq!(res.res.code, Code::Ok);
+        assert_eq!(res.res.data, None);
+        assert_eq!(res.res.log, "".to_string());
+        assert_eq!(res.gas_used, 100);
+        assert_eq!(res.gas_wanted, 101);
+        assert_eq!(res.height, 1234);
+        assert_eq!(res.tx_hash, "TX_HASH_0".to_string());
+
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_test").unwrap(),
+            "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()
+        );
+
+        let report = cosm_orc.gas_profiler_report().unwrap();
+        assert_eq!(report.keys().len(), 1);
+        assert_eq!(report.get("cw_test").unwrap().keys().len(), 1);
+
+        let r = report
+            .get("cw_test")
+            .unwrap()
+            .get("Instantiate__i_test")
+            .unwrap();
+        assert_eq!(r.gas_used, 100);
+        assert_eq!(r.gas_wanted, 101);
+        assert_eq!(r.endpoint.as_deref(), Some("http://localhost:9090/"));
+
+        let fee = vec![Coin {
+            denom: "utest".parse().unwrap(),
+            amount: 11,
+        }];
+        assert_eq!(r.fee, fee);
+        assert_eq!(cosm_orc.fee_report().unwrap().per_contract["cw_test"], fee);
+    }
+
+    #[test]
+    fn execute_fails_over_to_healthy_endpoint() {
+        let cfg = test_cfg();
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let unavailable = || ChainError::CosmosSdk {
+            res: ChainResponse {
+                code: Code::Err(14),
+                data: None,
+                log: "status: 503 Service Unavailable".to_string(),
+            },
+        };
+
+        let mut down = MockCosmosClient::new();
+        down.expect_query::<QueryAccountRequest, QueryAccountResponse>()
+            .times(1)
+            .returning(move |_, _| Err(unavailable()));
+
+        let mut unhealthy = MockCosmosClient::new();
+        unhealthy
+            .expect_query::<GetLatestBlockRequest, GetLatestBlockResponse>()
+            .times(1)
+            .returning(move |_, _| Err(unavailable()));
+
+        let mut healthy = MockCosmosClient::new();
+        healthy
+            .expect_query::<GetLatestBlockRequest, GetLatestBlockResponse>()
+            .times(1)
+            .returning(|_, _| {
+                Ok(GetLatestBlockResponse {
+                    block_id: Some(Default::default()),
+                    block: Some(Default::default()),
+                })
+            });
+        mock_tx_setup(&mut healthy, 1);
+        healthy.expect_broadcast_tx_block().times(1).returning(|_| {
+            Ok(ChainTxResponse {
+                res: ChainResponse {
+                    code: Code::Ok,
+                    data: None,
+                    log: "".to_string(),
+                },
+                events: vec![],
+                gas_wanted: 101,
+                gas_used: 100,
+                tx_hash: "TX_HASH_0".to_string(),
+                height: 1234,
+            })
+        });
+
+        let client = FailoverClient::with_clients(
+            &cfg,
+            vec![
+                ("http://node-1:9090/".to_string(), down),
+                ("http://node-2:9090/".to_string(), unhealthy),
+                ("http://node-3:9090/".to_string(), healthy),
+            ],
+        )
+        .unwrap();
+        let endpoint = client.active_endpoint().clone();
+        assert_eq!(endpoint.url(), "http://node-1:9090/");
+
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(HashMap::from([(
+                "cw_test".to_string(),
+                DeployInfo {
+                    code_id: Some(1337),
+                    address: Some("juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()),
+                },
+            )])),
+            gas_profiler: Some(GasProfiler::new()),
+            ..test_cosm_orc(client)
+        };
+        cosm_orc.set_endpoint(endpoint.clone());
+
+        let res = cosm_orc
+            .execute("cw_test", "e_test", &TestMsg {}, &key, vec![])
+            .unwrap();
+        assert_eq!(res.res.tx_hash, "TX_HASH_0");
+
+        assert_eq!(endpoint.url(), "http://node-3:9090/");
+        assert_eq!(cosm_orc.endpoint(), Some("http://node-3:9090/"));
+
+        let report = cosm_orc.gas_profiler_report().unwrap();
+        let r = report
+            .get("cw_test")
+            .unwrap()
+            .get("Execute__e_test")
+            .unwrap();
+        assert_eq!(r.endpoint.as_deref(), Some("http://node-3:9090/"));
+    }
+
+    #[test]
+    fn execute_not_stored() {
+        let cfg = test_cfg();
+        let code_ids = HashMap::new();
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(code_ids),
+            ..test_cosm_orc(MockCosmosClient::new())
+        };
+
+        let res = cosm_orc.execute("cw_not_stored", "e_test", &TestMsg {}, &key, vec![]);
+
+        assert_matches!(
+            res.unwrap_err(),
+            ProcessError::ContractMapError(e) if e == ContractMapError::NotStored{name: "cw_not_stored".to_string()}
+        );
+
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_not_stored").unwrap_err(),
+            ContractMapError::NotStored {
+                name: "cw_not_stored".to_string()
+            }
+        );
+
+        assert_eq!(cosm_orc.gas_profiler_report(), None);
+    }
+
+    #[test]
+    fn execute_not_initialized() {
+        let cfg = test_cfg();
+        let code_ids = HashMap::from([(
+            "cw_not_init".to_string(),
+            DeployInfo {
+                code_id: Some(1337),
+                address: None,
+            },
+        )]);
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(code_ids),
+            ..test_cosm_orc(MockCosmosClient::new())
+        };
+
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_not_init").unwrap_err(),
+            ContractMapError::NotDeployed {
+                name: "cw_not_init".to_string()
+            }
+        );
+
+        let res = cosm_orc.execute("cw_not_init", "e_test", &TestMsg {}, &key, vec![]);
+
+        assert_matches!(
+            res.unwrap_err(),
+            ProcessError::ContractMapError(e) if e == ContractMapError::NotDeployed{name: "cw_not_init".to_string()}
+        );
+
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_not_init").unwrap_err(),
+            ContractMapError::NotDeployed {
+                name: "cw_not_init".to_string()
+            }
+        );
+
+        assert_eq!(cosm_orc.gas_profiler_report(), None);
+    }
+
+    #[test]
+    fn execute_cosmossdk_error() {
+        let cfg = test_cfg();
+        let code_ids = HashMap::from([(
+            "cw_test".to_string(),
+            DeployInfo {
+                code_id: Some(1337),
+                address: None,
+            },
+        )]);
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let msg = &TestMsg {};
+
+        let mut mock_client = MockCosmosClient::new();
+
+        mock_client
+            .expect_query::<QueryAccountRequest, QueryAccountResponse>()
+            .times(1)
+            .returning(move |_, t: &str| {
+                Ok(QueryAccountResponse {
+                    account: Some(cosmos_sdk_proto::Any {
+                        type_url: t.to_owned(),
+                        value: BaseAccount {
+                            address: "juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg".to_string(),
+                            pub_key: None,
+                            account_number: 1221,
+                            sequence: 1,
+                        }
+                        .to_bytes()
+                        .unwrap(),
+                    }),
+                })
+            });
+
+        mock_client.expect_simulate_tx().times(1).returning(|_| {
+            Ok(GasInfo {
+                gas_wanted: 200u16.into(),
+                gas_used: 100u16.into(),
+            })
+        });
+
+        mock_client
+            .expect_broadcast_tx_block()
+            .times(1)
+            .returning(|_| {
+                Ok(ChainTxResponse {
+                    res: ChainResponse {
+                        code: Code::Ok,
+                        data: Some(vec![]),
+                        log: "".to_string(),
+                    },
+                    events: vec![Event {
+                        type_str: "instantiate".to_string(),
+                        attributes: vec![Tag {
+                            key: "_contract_address".to_string(),
+                            value: "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string(),
+                        }],
+                    }],
+                    gas_wanted: 101,
+                    gas_used: 100,
+                    tx_hash: "TX_HASH_0".to_string(),
+                    height: 1234,
+                })
+            });
+
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(code_ids),
+            ..test_cosm_orc(mock_client)
+        };
+
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_test").unwrap_err(),
+            ContractMapError::NotDeployed {
+                name: "cw_test".to_string()
+            }
+        );
+
+        let res = cosm_orc
+            .instantiate("cw_test", "i_test", msg, &key, None, vec![])
+            .unwrap()
+            .res;
+
+        assert_eq!(res.res.code, Code::Ok);
+        assert_eq!(res.res.data, Some(vec![]));
+        assert_eq!(res.res.log, "".to_string());
+        assert_eq!(res.gas_used, 100);
+        assert_eq!(res.gas_wanted, 101);
+
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_test").unwrap(),
+            "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()
+        );
+
+        let mut mock_client = MockCosmosClient::new();
+
+        mock_client
+            .expect_query::<QueryAccountRequest, QueryAccountResponse>()
+            .times(1)
+            .returning(move |_, _| {
+                Err(ChainError::CosmosSdk {
+                    res: ChainResponse {
+                        code: Code::Err(1),
+                        data: None,
+                        log: "error".to_string(),
+                    },
+                })
+            });
+
+        let mut cosm_orc = CosmOrc {
+            contract_map: cosm_orc.contract_map,
+            ..test_cosm_orc(mock_client)
+        };
+
+        let res = cosm_orc.execute("cw_test", "e_test", msg, &key, vec![]);
+
+        assert_matches!(
+            res.unwrap_err(),
+            ProcessError::CosmwasmError(CosmwasmError::TxError(TxError::AccountError(
+                AccountError::ChainError { .. }
+            )))
+        );
+
+        assert_eq!(cosm_orc.gas_profiler_report(), None);
+    }
+
+    #[test]
+    fn execute() {
+        let cfg = test_cfg();
+        let code_ids = HashMap::from([(
+            "cw_test".to_string(),
+            DeployInfo {
+                code_id: Some(1337),
+                address: None,
+            },
+        )]);
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let msg = &TestMsg {};
+
+        let mut mock_client = MockCosmosClient::new();
+
+        mock_client
+            .expect_query::<QueryAccountRequest, QueryAccountResponse>()
+            .times(2)
+            .returning(move |_, t: &str| {
+                Ok(QueryAccountResponse {
+                    account: Some(cosmos_sdk_proto::Any {
+                        type_url: t.to_owned(),
+                        value: BaseAccount {
+                            address: "juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg".to_string(),
+                            pub_key: None,
+                            account_number: 1221,
+                            sequence: 1,
+                        }
+                        .to_bytes()
+                        .unwrap(),
+                    }),
+                })
+            });
+
+        mock_client.expect_simulate_tx().times(2).returning(|_| {
+            Ok(GasInfo {
+                gas_wanted: 200u16.into(),
+                gas_used: 100u16.into(),
+            })
+        });
+
+        mock_client
+            .expect_broadcast_tx_block()
+            .times(2)
+            .returning(|_| {
+                Ok(ChainTxResponse {
+                    res: ChainResponse {
+                        code: Code::Ok,
+                        data: Some(vec![]),
+                        log: "".to_string(),
+                    },
+                    events: vec![Event {
+                        type_str: "instantiate".to_string(),
+                        attributes: vec![Tag {
+                            key: "_contract_address".to_string(),
+                            value: "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string(),
+                        }],
+                    }],
+                    gas_wanted: 101,
+                    gas_used: 100,
+                    tx_hash: "TX_HASH_0".to_string(),
+                    height: 1234,
+                })
+            });
+
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(code_ids),
+            ..test_cosm_orc(mock_client)
+        };
+
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_test").unwrap_err(),
+            ContractMapError::NotDeployed {
+                name: "cw_test".to_string()
+            }
+        );
+
+        let res = cosm_orc
+            .instantiate("cw_test", "i_test", msg, &key, None, vec![])
+            .unwrap()
+            .res;
+
+        assert_eq!(res.res.code, Code::Ok);
+        assert_eq!(res.res.data, Some(vec![]));
+        assert_eq!(res.res.log, "".to_string());
+        assert_eq!(res.gas_used, 100);
+        assert_eq!(res.gas_wanted, 101);
+
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_test").unwrap(),
+            "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()
+        );
+        assert_eq!(cosm_orc.gas_profiler_report(), None);
+
+        let res = cosm_orc
+            .execute("cw_test", "e_test", msg, &key, vec![])
+            .unwrap()
+            .res;
+
+        assert_eq!(res.res.code, Code::Ok);
+        assert_eq!(res.res.data, Some(vec![]));
+        assert_eq!(res.res.log, "".to_string());
+        assert_eq!(res.gas_used, 100);
+        assert_eq!(res.gas_wanted, 101);
+
+        assert_eq!(cosm_orc.gas_profiler_report(), None);
+    }
+
+    #[test]
+    fn execute_with_profiler() {
+        let cfg = test_cfg();
+        let code_ids = HashMap::from([(
+            "cw_test".to_string(),
+            DeployInfo {
+                code_id: Some(1337),
+                address: None,
+            },
+        )]);
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let msg = &TestMsg {};
+
+        let mut mock_client = MockCosmosClient::new();
+
+        mock_client
+            .expect_query::<QueryAccountRequest, QueryAccountResponse>()
+            .times(2)
+            .returning(move |_, t: &str| {
+                Ok(QueryAccountResponse {
+                    account: Some(cosmos_sdk_proto::Any {
+                        type_url: t.to_owned(),
+                        value: BaseAccount {
+                            address: "juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg".to_string(),
+                            pub_key: None,
+                            account_number: 1221,
+                            sequence: 1,
                         }
-                        time::sleep(Duration::from_millis(500)).await;
-                    }
-                }
-
-                let mut curr_height = self
-                    .client
-                    .tendermint_query_latest_block()
-                    .await?
-                    .block
-                    .header
-                    .unwrap()
-                    .height as u64;
-
-                let target_height = curr_height + n;
-
-                while curr_height < target_height {
-                    time::sleep(Duration::from_millis(500)).await;
-
-                    curr_height = self
-                        .client
-                        .tendermint_query_latest_block()
-                        .await?
-                        .block
-                        .header
-                        .unwrap()
-                        .height as u64;
-                }
+                        .to_bytes()
+                        .unwrap(),
+                    }),
+                })
+            });
 
-                Ok(())
+        mock_client.expect_simulate_tx().times(2).returning(|_| {
+            Ok(GasInfo {
+                gas_wanted: 200u16.into(),
+                gas_used: 100u16.into(),
             })
-            .await
-        })??;
-
-        Ok(())
-    }
+        });
 
-    /// Blocks the current thread until `n` block header seconds have passed.
-    ///
-    /// Local system time can differ from block header time, so this can be used to reliably wait n block seconds
-    /// before some action can be taken in a smart contract.
-    ///
-    /// # Arguments
-    /// * `n` - Wait for this number of block header seconds to pass.
-    /// * `timeout` - Throws `PollBlockError` once `timeout` has elapsed.
-    pub fn poll_for_n_secs<T: Into<Duration> + Send>(
-        &self,
-        n: u64,
-        timeout: T,
-    ) -> Result<(), PollBlockError> {
-        tokio_block(async {
-            _timeout(timeout.into(), async {
-                let mut curr_time_secs = self
-                    .client
-                    .tendermint_query_latest_block()
-                    .await?
-                    .block
-                    .header
-                    .unwrap()
-                    .time
-                    .unwrap()
-                    .seconds as u64;
+        mock_client
+            .expect_broadcast_tx_block()
+            .times(2)
+            .returning(|_| {
+                Ok(ChainTxResponse {
+                    res: ChainResponse {
+                        code: Code::Ok,
+                        data: Some(vec![]),
+                        log: "".to_string(),
+                    },
+                    events: vec![Event {
+                        type_str: "instantiate".to_string(),
+                        attributes: vec![Tag {
+                            key: "_contract_address".to_string(),
+                            value: "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string(),
+                        }],
+                    }],
+                    gas_wanted: 101,
+                    gas_used: 100,
+                    tx_hash: "TX_HASH_0".to_string(),
+                    height: 1234,
+                })
+            });
 
-                let target_time = curr_time_secs + n;
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(code_ids),
+            gas_profiler: Some(GasProfiler::new()),
+            ..test_cosm_orc(mock_client)
+        };
 
-                while curr_time_secs < target_time {
-                    time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_test").unwrap_err(),
+            ContractMapError::NotDeployed {
+                name: "cw_test".to_string()
+            }
+        );
 
-                    curr_time_secs = self
-                        .client
-                        .tendermint_query_latest_block()
-                        .await?
-                        .block
-                        .header
-                        .unwrap()
-                        .time
-                        .unwrap()
-                        .seconds as u64;
-                }
+        let res = cosm_orc
+            .instantiate("cw_test", "i_test", msg, &key, None, vec![])
+            .unwrap()
+            .res;
 
-                Ok::<(), PollBlockError>(())
-            })
-            .await
-        })??;
+        assert_eq!(res.res.code, Code::Ok);
+        assert_eq!(res.res.data, Some(vec![]));
+        assert_eq!(res.res.log, "".to_string());
+        assert_eq!(res.gas_used, 100);
+        assert_eq!(res.gas_wanted, 101);
 
-        Ok(())
-    }
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_test").unwrap(),
+            "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()
+        );
 
-    /// Get gas usage report
-    pub fn gas_profiler_report(&self) -> Option<&Report> {
-        self.gas_profiler.as_ref().map(|p| p.report())
-    }
-}
+        let report = cosm_orc.gas_profiler_report().unwrap();
+        assert_eq!(report.keys().len(), 1);
+        assert_eq!(report.get("cw_test").unwrap().keys().len(), 1);
 
-pub fn tokio_block<F: Future>(f: F) -> F::Output {
-    tokio::runtime::Builder::new_current_thread()
-        .enable_all()
-        .build()
-        .unwrap()
-        .block_on(f)
-}
+        let r = report
+            .get("cw_test")
+            .unwrap()
+            .get("Instantiate__i_test")
+            .unwrap();
+        assert_eq!(r.gas_used, 100);
+        assert_eq!(r.gas_wanted, 101);
 
-#[cfg(test)]
-mod tests {
-    use super::CosmOrc;
-    use crate::orchestrator::deploy::DeployInfo;
-    use crate::orchestrator::gas_profiler::GasProfiler;
-    use crate::orchestrator::{
-        deploy::ContractMap,
-        error::{ContractMapError, ProcessError, StoreError},
-    };
-    use assert_matches::assert_matches;
-    use cosm_tome::chain::error::ChainError;
-    use cosm_tome::chain::fee::GasInfo;
-    use cosm_tome::chain::request::TxOptions;
-    use cosm_tome::chain::response::{ChainResponse, ChainTxResponse, Code, Event, Tag};
-    use cosm_tome::clients::client::{CosmTome, MockCosmosClient};
-    use cosm_tome::config::cfg::ChainConfig;
-    use cosm_tome::modules::auth::error::AccountError;
-    use cosm_tome::modules::cosmwasm::error::CosmwasmError;
-    use cosm_tome::modules::tx::error::TxError;
-    use cosm_tome::signing_key::key::SigningKey;
-    use cosmos_sdk_proto::cosmos::auth::v1beta1::{
-        BaseAccount, QueryAccountRequest, QueryAccountResponse,
-    };
-    use cosmos_sdk_proto::cosmwasm::wasm::v1::{
-        QuerySmartContractStateRequest, QuerySmartContractStateResponse,
-    };
-    use cosmos_sdk_proto::traits::MessageExt;
-    use serde::Serialize;
-    use std::collections::HashMap;
-    use std::vec;
+        let res = cosm_orc
+            .execute("cw_test", "e_test", msg, &key, vec![])
+            .unwrap()
+            .res;
+        assert_eq!(res.res.code, Code::Ok);
+        assert_eq!(res.res.data, Some(vec![]));
+        assert_eq!(res.res.log, "".to_string());
+        assert_eq!(res.gas_used, 100);
+        assert_eq!(res.gas_wanted, 101);
 
-    #[derive(Serialize)]
-    pub struct TestMsg {}
+        let report = cosm_orc.gas_profiler_report().unwrap();
+        assert_eq!(report.keys().len(), 1);
+        assert_eq!(report.get("cw_test").unwrap().keys().len(), 2);
 
-    pub fn test_cfg() -> ChainConfig {
-        ChainConfig {
-            denom: "utest".to_string(),
-            prefix: "test".to_string(),
-            chain_id: "test-1".to_string(),
-            rpc_endpoint: None,
-            grpc_endpoint: Some("localhost:12690".to_string()),
-            derivation_path: "m/44'/118'/0'/0/0".to_string(),
-            gas_price: 0.1,
-            gas_adjustment: 1.5,
-        }
+        let r = report
+            .get("cw_test")
+            .unwrap()
+            .get("Execute__e_test")
+            .unwrap();
+        assert_eq!(r.gas_used, 100);
+        assert_eq!(r.gas_wanted, 101);
     }
 
     #[test]
-    fn instantiate_not_stored() {
-        let cfg = test_cfg();
-        let code_ids = HashMap::new();
-        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
-
-        let mut cosm_orc = CosmOrc {
-            contract_map: ContractMap::new(code_ids),
-            client: CosmTome::new(cfg, MockCosmosClient::new()),
-            gas_profiler: None,
-            tx_options: TxOptions::default(),
-        };
+    fn query_not_stored() {
+        let cosm_orc = test_cosm_orc(MockCosmosClient::new());
 
-        let res = cosm_orc.instantiate("cw_not_stored", "i_test", &TestMsg {}, &key, None, vec![]);
+        let res = cosm_orc.query("cw_not_stored", &TestMsg {});
 
         assert_matches!(
             res.unwrap_err(),
@@ -640,54 +3039,38 @@ mod tests {
     }
 
     #[test]
-    fn instantiate_cosmossdk_error() {
-        let cfg = test_cfg();
+    fn query_not_initialized() {
         let code_ids = HashMap::from([(
-            "cw_test".to_string(),
+            "cw_not_init".to_string(),
             DeployInfo {
                 code_id: Some(1337),
                 address: None,
             },
         )]);
-        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
-
-        let msg = &TestMsg {};
-
-        let mut mock_client = MockCosmosClient::new();
-
-        mock_client
-            .expect_query::<QueryAccountRequest, QueryAccountResponse>()
-            .times(1)
-            .returning(move |_, _| {
-                Err(ChainError::CosmosSdk {
-                    res: ChainResponse {
-                        code: Code::Err(1),
-                        data: None,
-                        log: "error".to_string(),
-                    },
-                })
-            });
 
-        let mut cosm_orc = CosmOrc {
+        let cosm_orc = CosmOrc {
             contract_map: ContractMap::new(code_ids),
-            client: CosmTome::new(cfg, mock_client),
-            gas_profiler: None,
-            tx_options: TxOptions::default(),
+            ..test_cosm_orc(MockCosmosClient::new())
         };
 
-        let res = cosm_orc.instantiate("cw_test", "i_test", msg, &key, None, vec![]);
+        assert_eq!(
+            cosm_orc.contract_map.address("cw_not_init").unwrap_err(),
+            ContractMapError::NotDeployed {
+                name: "cw_not_init".to_string()
+            }
+        );
+
+        let res = cosm_orc.query("cw_not_init", &TestMsg {});
 
         assert_matches!(
             res.unwrap_err(),
-            ProcessError::CosmwasmError(CosmwasmError::TxError(TxError::AccountError(
-                AccountError::ChainError(ChainError::CosmosSdk { .. })
-            )))
+            ProcessError::ContractMapError(e) if e == ContractMapError::NotDeployed{name: "cw_not_init".to_string()}
         );
 
         assert_eq!(
-            cosm_orc.contract_map.address("cw_test").unwrap_err(),
+            cosm_orc.contract_map.address("cw_not_init").unwrap_err(),
             ContractMapError::NotDeployed {
-                name: "cw_test".to_string()
+                name: "cw_not_init".to_string()
             }
         );
 
@@ -695,7 +3078,7 @@ mod tests {
     }
 
     #[test]
-    fn instantiate() {
+    fn query_cosmossdk_error() {
         let cfg = test_cfg();
         let code_ids = HashMap::from([(
             "cw_test".to_string(),
@@ -743,8 +3126,8 @@ mod tests {
                 Ok(ChainTxResponse {
                     res: ChainResponse {
                         code: Code::Ok,
-                        data: None,
-                        log: "log log log".to_string(),
+                        data: Some(vec![]),
+                        log: "".to_string(),
                     },
                     events: vec![Event {
                         type_str: "instantiate".to_string(),
@@ -760,11 +3143,22 @@ mod tests {
                 })
             });
 
+        mock_client
+            .expect_query::<QuerySmartContractStateRequest, QuerySmartContractStateResponse>()
+            .times(1)
+            .returning(move |_, _| {
+                Err(ChainError::CosmosSdk {
+                    res: ChainResponse {
+                        code: Code::Err(1),
+                        data: None,
+                        log: "error".to_string(),
+                    },
+                })
+            });
+
         let mut cosm_orc = CosmOrc {
             contract_map: ContractMap::new(code_ids),
-            client: CosmTome::new(cfg, mock_client),
-            gas_profiler: None,
-            tx_options: TxOptions::default(),
+            ..test_cosm_orc(mock_client)
         };
 
         assert_eq!(
@@ -780,10 +3174,8 @@ mod tests {
             .res;
 
         assert_eq!(res.res.code, Code::Ok);
-        assert_eq!(res.res.data, None);
-        assert_eq!(res.res.log, "log log log".to_string());
-        assert_eq!(res.height, 1234);
-        assert_eq!(res.tx_hash, "TX_HASH_0".to_string());
+        assert_eq!(res.res.data, Some(vec![]));
+        assert_eq!(res.res.log, "".to_string());
         assert_eq!(res.gas_used, 100);
         assert_eq!(res.gas_wanted, 101);
 
@@ -792,11 +3184,15 @@ mod tests {
             "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()
         );
 
+        let res = cosm_orc.query("cw_test", msg);
+
+        assert_matches!(res.unwrap_err(), ProcessError::CosmwasmError(..));
+
         assert_eq!(cosm_orc.gas_profiler_report(), None);
     }
 
     #[test]
-    fn instantiate_with_profiler() {
+    fn query() {
         let cfg = test_cfg();
         let code_ids = HashMap::from([(
             "cw_test".to_string(),
@@ -844,7 +3240,7 @@ mod tests {
                 Ok(ChainTxResponse {
                     res: ChainResponse {
                         code: Code::Ok,
-                        data: None,
+                        data: Some(vec![]),
                         log: "".to_string(),
                     },
                     events: vec![Event {
@@ -861,11 +3257,14 @@ mod tests {
                 })
             });
 
+        mock_client
+            .expect_query::<QuerySmartContractStateRequest, QuerySmartContractStateResponse>()
+            .times(1)
+            .returning(move |_, _| Ok(QuerySmartContractStateResponse { data: vec![] }));
+
         let mut cosm_orc = CosmOrc {
             contract_map: ContractMap::new(code_ids),
-            client: CosmTome::new(cfg, mock_client),
-            gas_profiler: Some(GasProfiler::new()),
-            tx_options: TxOptions::default(),
+            ..test_cosm_orc(mock_client)
         };
 
         assert_eq!(
@@ -881,118 +3280,203 @@ mod tests {
             .res;
 
         assert_eq!(res.res.code, Code::Ok);
-        assert_eq!(res.res.data, None);
+        assert_eq!(res.res.data, Some(vec![]));
         assert_eq!(res.res.log, "".to_string());
         assert_eq!(res.gas_used, 100);
         assert_eq!(res.gas_wanted, 101);
-        assert_eq!(res.height, 1234);
-        assert_eq!(res.tx_hash, "TX_HASH_0".to_string());
 
         assert_eq!(
             cosm_orc.contract_map.address("cw_test").unwrap(),
             "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()
         );
+        assert_eq!(cosm_orc.gas_profiler_report(), None);
 
-        let report = cosm_orc.gas_profiler_report().unwrap();
-        assert_eq!(report.keys().len(), 1);
-        assert_eq!(report.get("cw_test").unwrap().keys().len(), 1);
+        let res = cosm_orc.query("cw_test", msg).unwrap();
+        assert_eq!(res.res.code, Code::Ok);
+        assert_eq!(res.res.data, Some(vec![]));
+        assert_eq!(res.res.log, "".to_string());
 
-        let r = report
-            .get("cw_test")
-            .unwrap()
-            .get("Instantiate__i_test")
-            .unwrap();
-        assert_eq!(r.gas_used, 100);
-        assert_eq!(r.gas_wanted, 101);
+        assert_eq!(cosm_orc.gas_profiler_report(), None);
     }
 
     #[test]
-    fn execute_not_stored() {
-        let cfg = test_cfg();
-        let code_ids = HashMap::new();
-        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+    fn poll_until() {
+        let code_ids = HashMap::from([(
+            "cw_test".to_string(),
+            DeployInfo {
+                code_id: Some(1337),
+                address: Some("juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()),
+            },
+        )]);
 
-        let mut cosm_orc = CosmOrc {
-            contract_map: ContractMap::new(code_ids),
-            client: CosmTome::new(cfg, MockCosmosClient::new()),
-            gas_profiler: None,
-            tx_options: TxOptions::default(),
-        };
+        let msg = &TestMsg {};
 
-        let res = cosm_orc.execute("cw_not_stored", "e_test", &TestMsg {}, &key, vec![]);
+        let mut mock_client = MockCosmosClient::new();
 
-        assert_matches!(
-            res.unwrap_err(),
-            ProcessError::ContractMapError(e) if e == ContractMapError::NotStored{name: "cw_not_stored".to_string()}
-        );
+        let mut count = 0u8;
+        mock_client
+            .expect_query::<QuerySmartContractStateRequest, QuerySmartContractStateResponse>()
+            .times(3)
+            .returning(move |_, _| {
+                count += 1;
+                Ok(QuerySmartContractStateResponse { data: vec![count] })
+            });
 
-        assert_eq!(
-            cosm_orc.contract_map.address("cw_not_stored").unwrap_err(),
-            ContractMapError::NotStored {
-                name: "cw_not_stored".to_string()
-            }
-        );
+        let cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(code_ids),
+            ..test_cosm_orc(mock_client)
+        };
 
-        assert_eq!(cosm_orc.gas_profiler_report(), None);
+        let res = cosm_orc
+            .poll_until(
+                "cw_test",
+                msg,
+                |res| res.res.data == Some(vec![3]),
+                Duration::from_millis(1),
+                Duration::from_secs(5),
+            )
+            .unwrap();
+
+        assert_eq!(res.res.code, Code::Ok);
+        assert_eq!(res.res.data, Some(vec![3]));
     }
 
     #[test]
-    fn execute_not_initialized() {
-        let cfg = test_cfg();
+    fn poll_until_timeout() {
         let code_ids = HashMap::from([(
-            "cw_not_init".to_string(),
+            "cw_test".to_string(),
             DeployInfo {
                 code_id: Some(1337),
-                address: None,
+                address: Some("juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()),
             },
         )]);
-        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
 
-        let mut cosm_orc = CosmOrc {
+        let msg = &TestMsg {};
+
+        let mut mock_client = MockCosmosClient::new();
+
+        mock_client
+            .expect_query::<QuerySmartContractStateRequest, QuerySmartContractStateResponse>()
+            .returning(move |_, _| Ok(QuerySmartContractStateResponse { data: vec![] }));
+
+        let cosm_orc = CosmOrc {
             contract_map: ContractMap::new(code_ids),
-            client: CosmTome::new(cfg, MockCosmosClient::new()),
-            gas_profiler: None,
-            tx_options: TxOptions::default(),
+            ..test_cosm_orc(mock_client)
         };
 
-        assert_eq!(
-            cosm_orc.contract_map.address("cw_not_init").unwrap_err(),
-            ContractMapError::NotDeployed {
-                name: "cw_not_init".to_string()
-            }
+        let res = cosm_orc.poll_until(
+            "cw_test",
+            msg,
+            |_| false,
+            Duration::from_millis(10),
+            Duration::from_millis(50),
         );
 
-        let res = cosm_orc.execute("cw_not_init", "e_test", &TestMsg {}, &key, vec![]);
+        assert_matches!(res.unwrap_err(), PollQueryError::Timeout(..));
+    }
+
+    fn latest_block(header: Option<Header>) -> GetLatestBlockResponse {
+        GetLatestBlockResponse {
+            block_id: Some(BlockId::default()),
+            block: Some(Block {
+                header,
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn poll_for_n_blocks_missing_header() {
+        let mut mock_client = MockCosmosClient::new();
+
+        mock_client
+            .expect_query::<GetLatestBlockRequest, GetLatestBlockResponse>()
+            .times(1)
+            .returning(move |_, _| Ok(latest_block(None)));
+
+        let cosm_orc = test_cosm_orc(mock_client);
+
+        let res = cosm_orc.poll_for_n_blocks(1, Duration::from_secs(5), false);
+        assert_matches!(res.unwrap_err(), PollBlockError::MissingBlockHeader);
+    }
+
+    #[test]
+    fn poll_for_n_blocks_tolerates_errors() {
+        let mut mock_client = MockCosmosClient::new();
+
+        let mut count = 0;
+        mock_client
+            .expect_query::<GetLatestBlockRequest, GetLatestBlockResponse>()
+            .times(5)
+            .returning(move |_, _| {
+                count += 1;
+                if count <= 2 {
+                    return Ok(latest_block(None));
+                }
+
+                Ok(latest_block(Some(Header {
+                    height: 10 + count,
+                    ..Default::default()
+                })))
+            });
+
+        let cosm_orc = test_cosm_orc(mock_client);
+
+        let policy = PollPolicy {
+            interval: Duration::from_millis(1),
+            max_errors: 2,
+            ..Default::default()
+        };
+
+        cosm_orc
+            .poll_for_n_blocks_with_policy(2, Duration::from_secs(5), false, &policy)
+            .unwrap();
+    }
+
+    #[test]
+    fn poll_for_n_blocks_subscribe_missing_rpc_endpoint() {
+        let cosm_orc = CosmOrc {
+            poll_policy: PollPolicy {
+                subscribe: true,
+                ..Default::default()
+            },
+            ..test_cosm_orc(MockCosmosClient::new())
+        };
 
+        let res = cosm_orc.poll_for_n_blocks(1, Duration::from_secs(5), false);
         assert_matches!(
             res.unwrap_err(),
-            ProcessError::ContractMapError(e) if e == ContractMapError::NotDeployed{name: "cw_not_init".to_string()}
+            PollBlockError::SubscribeError(SubscribeError::MissingRpcEndpoint)
         );
+    }
 
-        assert_eq!(
-            cosm_orc.contract_map.address("cw_not_init").unwrap_err(),
-            ContractMapError::NotDeployed {
-                name: "cw_not_init".to_string()
-            }
-        );
+    #[test]
+    fn poll_for_n_secs_missing_time() {
+        let mut mock_client = MockCosmosClient::new();
 
-        assert_eq!(cosm_orc.gas_profiler_report(), None);
+        mock_client
+            .expect_query::<GetLatestBlockRequest, GetLatestBlockResponse>()
+            .times(1)
+            .returning(move |_, _| Ok(latest_block(Some(Header::default()))));
+
+        let cosm_orc = test_cosm_orc(mock_client);
+
+        let res = cosm_orc.poll_for_n_secs(1, Duration::from_secs(5));
+        assert_matches!(res.unwrap_err(), PollBlockError::MissingBlockTime);
     }
 
     #[test]
-    fn execute_cosmossdk_error() {
+    fn send_to_contract_with_profiler() {
         let cfg = test_cfg();
         let code_ids = HashMap::from([(
             "cw_test".to_string(),
             DeployInfo {
                 code_id: Some(1337),
-                address: None,
+                address: Some("juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()),
             },
         )]);
         let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
 
-        let msg = &TestMsg {};
-
         let mut mock_client = MockCosmosClient::new();
 
         mock_client
@@ -1031,13 +3515,7 @@ mod tests {
                         data: Some(vec![]),
                         log: "".to_string(),
                     },
-                    events: vec![Event {
-                        type_str: "instantiate".to_string(),
-                        attributes: vec![Tag {
-                            key: "_contract_address".to_string(),
-                            value: "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string(),
-                        }],
-                    }],
+                    events: vec![],
                     gas_wanted: 101,
                     gas_used: 100,
                     tx_hash: "TX_HASH_0".to_string(),
@@ -1047,87 +3525,197 @@ mod tests {
 
         let mut cosm_orc = CosmOrc {
             contract_map: ContractMap::new(code_ids),
-            client: CosmTome::new(cfg.clone(), mock_client),
-            gas_profiler: None,
-            tx_options: TxOptions::default(),
+            gas_profiler: Some(GasProfiler::new()),
+            ..test_cosm_orc(mock_client)
         };
 
-        assert_eq!(
-            cosm_orc.contract_map.address("cw_test").unwrap_err(),
-            ContractMapError::NotDeployed {
-                name: "cw_test".to_string()
-            }
-        );
-
         let res = cosm_orc
-            .instantiate("cw_test", "i_test", msg, &key, None, vec![])
+            .send(
+                "cw_test",
+                "fund_contract",
+                vec![Coin {
+                    denom: "utest".parse().unwrap(),
+                    amount: 100,
+                }],
+                &key,
+            )
             .unwrap()
             .res;
 
         assert_eq!(res.res.code, Code::Ok);
-        assert_eq!(res.res.data, Some(vec![]));
-        assert_eq!(res.res.log, "".to_string());
         assert_eq!(res.gas_used, 100);
         assert_eq!(res.gas_wanted, 101);
 
+        let report = cosm_orc.gas_profiler_report().unwrap();
+        let r = report
+            .get("cw_test")
+            .unwrap()
+            .get("Send__fund_contract")
+            .unwrap();
+        assert_eq!(r.gas_used, 100);
+        assert_eq!(r.gas_wanted, 101);
+    }
+
+    #[test]
+    fn send_invalid_recipient() {
+        let cfg = test_cfg();
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let mut cosm_orc = CosmOrc {
+            gas_profiler: Some(GasProfiler::new()),
+            ..test_cosm_orc(MockCosmosClient::new())
+        };
+
+        let res = cosm_orc.send("cw_not_deployed", "send", vec![], &key);
+        assert_matches!(
+            res.unwrap_err(),
+            ProcessError::BankError(BankError::AccountError(..))
+        );
+
+        assert_eq!(cosm_orc.gas_profiler_report().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn send_to_undeployed_contract() {
+        let cfg = test_cfg();
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let code_ids = HashMap::from([(
+            "cw_not_init".to_string(),
+            DeployInfo {
+                code_id: Some(1337),
+                address: None,
+            },
+        )]);
+
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(code_ids),
+            ..test_cosm_orc(MockCosmosClient::new())
+        };
+
+        let res = cosm_orc.send("cw_not_init", "send", vec![], &key);
+        assert_matches!(
+            res.unwrap_err(),
+            ProcessError::ContractMapError(ContractMapError::NotDeployed { name }) if name == "cw_not_init"
+        );
+    }
+
+    #[test]
+    fn balance() {
+        let mut mock_client = MockCosmosClient::new();
+
+        mock_client
+            .expect_query::<QueryBalanceRequest, QueryBalanceResponse>()
+            .times(1)
+            .returning(|req, _| {
+                assert_eq!(req.address, "juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg");
+                Ok(QueryBalanceResponse {
+                    balance: Some(cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+                        denom: req.denom,
+                        amount: "1337".to_string(),
+                    }),
+                })
+            });
+
+        let cosm_orc = test_cosm_orc(mock_client);
+
+        let res = cosm_orc
+            .balance("juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg", "utest")
+            .unwrap();
+
         assert_eq!(
-            cosm_orc.contract_map.address("cw_test").unwrap(),
-            "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()
+            res,
+            Coin {
+                denom: "utest".parse().unwrap(),
+                amount: 1337
+            }
         );
+    }
+
+    #[test]
+    fn refund_accounts_continues_after_failures() {
+        let cfg = test_cfg();
+        let funder = SigningKey::random_mnemonic("funder".to_string(), cfg.derivation_path.clone());
+        let accounts = ["test_account_0", "test_account_1"].map(|name| {
+            let key = SigningKey::random_mnemonic(name.to_string(), cfg.derivation_path.clone());
+            let address = tokio_block(key.to_addr(&cfg.prefix)).unwrap();
+            TestAccount { key, address }
+        });
 
         let mut mock_client = MockCosmosClient::new();
 
+        let failing = accounts[0].address.to_string();
         mock_client
-            .expect_query::<QueryAccountRequest, QueryAccountResponse>()
+            .expect_query::<QueryBalanceRequest, QueryBalanceResponse>()
+            .times(2)
+            .returning(move |req, _| {
+                if req.address == failing {
+                    return Err(ChainError::CosmosSdk {
+                        res: ChainResponse {
+                            code: Code::Err(2),
+                            data: None,
+                            log: "error".to_string(),
+                        },
+                    });
+                }
+
+                Ok(QueryBalanceResponse {
+                    balance: Some(cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+                        denom: req.denom,
+                        amount: "1000000".to_string(),
+                    }),
+                })
+            });
+
+        mock_tx_setup(&mut mock_client, 1);
+        mock_client
+            .expect_broadcast_tx_block()
             .times(1)
-            .returning(move |_, _| {
-                Err(ChainError::CosmosSdk {
+            .returning(|_| {
+                Ok(ChainTxResponse {
                     res: ChainResponse {
-                        code: Code::Err(1),
+                        code: Code::Ok,
                         data: None,
-                        log: "error".to_string(),
+                        log: "".to_string(),
                     },
+                    events: vec![],
+                    gas_wanted: 101,
+                    gas_used: 100,
+                    tx_hash: "TX_HASH_0".to_string(),
+                    height: 1234,
                 })
             });
 
-        let mut cosm_orc = CosmOrc {
-            contract_map: cosm_orc.contract_map,
-            client: CosmTome::new(cfg, mock_client),
-            gas_profiler: None,
-            tx_options: TxOptions::default(),
-        };
+        let cosm_orc = test_cosm_orc(mock_client);
+        let factory = AccountFactory::new(
+            funder,
+            vec![Coin {
+                denom: "utest".parse().unwrap(),
+                amount: 1000,
+            }],
+        );
 
-        let res = cosm_orc.execute("cw_test", "e_test", msg, &key, vec![]);
+        // the refund tx gas limit is adjusted before the gas price is applied
+        let plan = cosm_orc.refund_plan(&factory).unwrap();
+        assert_eq!(plan.fee_reserve[0].amount, 30_000);
 
+        let err = cosm_orc.refund_accounts(&factory, &accounts).unwrap_err();
         assert_matches!(
-            res.unwrap_err(),
-            ProcessError::CosmwasmError(CosmwasmError::TxError(TxError::AccountError(
-                AccountError::ChainError { .. }
-            )))
+            err,
+            ProcessError::RefundFailed { errors } if errors.len() == 1 && errors[0].0 == accounts[0].address.to_string()
         );
-
-        assert_eq!(cosm_orc.gas_profiler_report(), None);
     }
 
     #[test]
-    fn execute() {
+    fn fund_accounts_with_single_tx() {
         let cfg = test_cfg();
-        let code_ids = HashMap::from([(
-            "cw_test".to_string(),
-            DeployInfo {
-                code_id: Some(1337),
-                address: None,
-            },
-        )]);
-        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
-
-        let msg = &TestMsg {};
+        let funder = SigningKey::random_mnemonic("funder".to_string(), cfg.derivation_path.clone());
 
         let mut mock_client = MockCosmosClient::new();
 
         mock_client
             .expect_query::<QueryAccountRequest, QueryAccountResponse>()
-            .times(2)
+            .times(1)
             .returning(move |_, t: &str| {
                 Ok(QueryAccountResponse {
                     account: Some(cosmos_sdk_proto::Any {
@@ -1144,7 +3732,7 @@ mod tests {
                 })
             });
 
-        mock_client.expect_simulate_tx().times(2).returning(|_| {
+        mock_client.expect_simulate_tx().times(1).returning(|_| {
             Ok(GasInfo {
                 gas_wanted: 200u16.into(),
                 gas_used: 100u16.into(),
@@ -1153,7 +3741,7 @@ mod tests {
 
         mock_client
             .expect_broadcast_tx_block()
-            .times(2)
+            .times(1)
             .returning(|_| {
                 Ok(ChainTxResponse {
                     res: ChainResponse {
@@ -1161,13 +3749,7 @@ mod tests {
                         data: Some(vec![]),
                         log: "".to_string(),
                     },
-                    events: vec![Event {
-                        type_str: "instantiate".to_string(),
-                        attributes: vec![Tag {
-                            key: "_contract_address".to_string(),
-                            value: "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string(),
-                        }],
-                    }],
+                    events: vec![],
                     gas_wanted: 101,
                     gas_used: 100,
                     tx_hash: "TX_HASH_0".to_string(),
@@ -1176,69 +3758,82 @@ mod tests {
             });
 
         let mut cosm_orc = CosmOrc {
-            contract_map: ContractMap::new(code_ids),
-            client: CosmTome::new(cfg, mock_client),
-            gas_profiler: None,
-            tx_options: TxOptions::default(),
+            gas_profiler: Some(GasProfiler::new()),
+            ..test_cosm_orc(mock_client)
         };
 
-        assert_eq!(
-            cosm_orc.contract_map.address("cw_test").unwrap_err(),
-            ContractMapError::NotDeployed {
-                name: "cw_test".to_string()
-            }
-        );
+        let factory = AccountFactory::new(
+            funder,
+            vec![Coin {
+                denom: "utest".parse().unwrap(),
+                amount: 1_000_000,
+            }],
+        )
+        .mnemonic("notice oak worry limit wrap speak medal online prefer cluster roof addict wrist behave treat actual wasp year salad speed social layer crew genius");
 
-        let res = cosm_orc
-            .instantiate("cw_test", "i_test", msg, &key, None, vec![])
+        let accounts = cosm_orc.fund_accounts(&factory, 3).unwrap();
+
+        assert_eq!(accounts.len(), 3);
+        assert_eq!(accounts[2].key.name, "test_account_2");
+        assert_eq!(accounts[2].key.derivation_path, "m/44'/118'/0'/0/2");
+        assert_eq!(accounts[0].address.prefix(), "test");
+        assert_ne!(accounts[0].address, accounts[1].address);
+
+        let report = cosm_orc.gas_profiler_report().unwrap();
+        assert!(report
+            .get("multiple_recipients")
             .unwrap()
-            .res;
+            .contains_key("Send__fund_accounts"));
+    }
 
-        assert_eq!(res.res.code, Code::Ok);
-        assert_eq!(res.res.data, Some(vec![]));
-        assert_eq!(res.res.log, "".to_string());
-        assert_eq!(res.gas_used, 100);
-        assert_eq!(res.gas_wanted, 101);
+    #[test]
+    fn execute_unknown_key_name() {
+        let mut cosm_orc = CosmOrc {
+            gas_profiler: Some(GasProfiler::new()),
+            ..test_cosm_orc(MockCosmosClient::new())
+        };
 
-        assert_eq!(
-            cosm_orc.contract_map.address("cw_test").unwrap(),
-            "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()
+        let res = cosm_orc.execute("cw_test", "exec_test", &TestMsg {}, "validator", vec![]);
+        assert_matches!(
+            res.unwrap_err(),
+            ProcessError::KeyError(KeyError::NotFound { name }) if name == "validator"
         );
-        assert_eq!(cosm_orc.gas_profiler_report(), None);
+    }
 
-        let res = cosm_orc
-            .execute("cw_test", "e_test", msg, &key, vec![])
-            .unwrap()
-            .res;
+    #[test]
+    fn with_client() {
+        let mut chain_cfg = test_cfg();
+        chain_cfg.grpc_endpoint = None;
+
+        let cfg = Config {
+            chain_cfg,
+            contract_deploy_info: HashMap::new(),
+            keys: HashMap::new(),
+            endpoints: vec![],
+            artifacts: ArtifactFilter::default(),
+        };
 
-        assert_eq!(res.res.code, Code::Ok);
-        assert_eq!(res.res.data, Some(vec![]));
-        assert_eq!(res.res.log, "".to_string());
-        assert_eq!(res.gas_used, 100);
-        assert_eq!(res.gas_wanted, 101);
+        let cosm_orc = CosmOrc::with_client(cfg.clone(), MockCosmosClient::new(), true).unwrap();
+        assert!(cosm_orc.gas_profiler_report().is_some());
 
-        assert_eq!(cosm_orc.gas_profiler_report(), None);
+        let mut cfg = cfg;
+        cfg.chain_cfg.gas_adjustment = 0.5;
+        assert_matches!(
+            CosmOrc::with_client(cfg, MockCosmosClient::new(), false).unwrap_err(),
+            InitError::ConfigError(ConfigError::InvalidGasAdjustment { .. })
+        );
     }
 
     #[test]
-    fn execute_with_profiler() {
+    fn signer_uses_default_key() {
         let cfg = test_cfg();
-        let code_ids = HashMap::from([(
-            "cw_test".to_string(),
-            DeployInfo {
-                code_id: Some(1337),
-                address: None,
-            },
-        )]);
         let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
 
-        let msg = &TestMsg {};
-
         let mut mock_client = MockCosmosClient::new();
 
         mock_client
             .expect_query::<QueryAccountRequest, QueryAccountResponse>()
-            .times(2)
+            .times(1)
             .returning(move |_, t: &str| {
                 Ok(QueryAccountResponse {
                     account: Some(cosmos_sdk_proto::Any {
@@ -1255,7 +3850,7 @@ mod tests {
                 })
             });
 
-        mock_client.expect_simulate_tx().times(2).returning(|_| {
+        mock_client.expect_simulate_tx().times(1).returning(|_| {
             Ok(GasInfo {
                 gas_wanted: 200u16.into(),
                 gas_used: 100u16.into(),
@@ -1264,7 +3859,7 @@ mod tests {
 
         mock_client
             .expect_broadcast_tx_block()
-            .times(2)
+            .times(1)
             .returning(|_| {
                 Ok(ChainTxResponse {
                     res: ChainResponse {
@@ -1272,13 +3867,7 @@ mod tests {
                         data: Some(vec![]),
                         log: "".to_string(),
                     },
-                    events: vec![Event {
-                        type_str: "instantiate".to_string(),
-                        attributes: vec![Tag {
-                            key: "_contract_address".to_string(),
-                            value: "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string(),
-                        }],
-                    }],
+                    events: vec![],
                     gas_wanted: 101,
                     gas_used: 100,
                     tx_hash: "TX_HASH_0".to_string(),
@@ -1286,159 +3875,207 @@ mod tests {
                 })
             });
 
-        let mut cosm_orc = CosmOrc {
-            contract_map: ContractMap::new(code_ids),
-            client: CosmTome::new(cfg, mock_client),
-            gas_profiler: Some(GasProfiler::new()),
-            tx_options: TxOptions::default(),
-        };
-
-        assert_eq!(
-            cosm_orc.contract_map.address("cw_test").unwrap_err(),
-            ContractMapError::NotDeployed {
-                name: "cw_test".to_string()
-            }
-        );
-
-        let res = cosm_orc
-            .instantiate("cw_test", "i_test", msg, &key, None, vec![])
-            .unwrap()
-            .res;
+        let mut cosm_orc = test_cosm_orc(mock_client);
 
-        assert_eq!(res.res.code, Code::Ok);
-        assert_eq!(res.res.data, Some(vec![]));
-        assert_eq!(res.res.log, "".to_string());
-        assert_eq!(res.gas_used, 100);
-        assert_eq!(res.gas_wanted, 101);
+        let amounts = vec![Coin {
+            denom: "utest".parse().unwrap(),
+            amount: 100,
+        }];
 
-        assert_eq!(
-            cosm_orc.contract_map.address("cw_test").unwrap(),
-            "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()
+        let res = cosm_orc.signer().send(
+            "juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg",
+            "send",
+            amounts.clone(),
+        );
+        assert_matches!(
+            res.unwrap_err(),
+            ProcessError::KeyError(KeyError::NoDefaultKey)
         );
 
-        let report = cosm_orc.gas_profiler_report().unwrap();
-        assert_eq!(report.keys().len(), 1);
-        assert_eq!(report.get("cw_test").unwrap().keys().len(), 1);
-
-        let r = report
-            .get("cw_test")
-            .unwrap()
-            .get("Instantiate__i_test")
-            .unwrap();
-        assert_eq!(r.gas_used, 100);
-        assert_eq!(r.gas_wanted, 101);
+        let res = cosm_orc.with_signer("alice").send(
+            "juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg",
+            "send",
+            amounts.clone(),
+        );
+        assert_matches!(
+            res.unwrap_err(),
+            ProcessError::KeyError(KeyError::NotFound { name }) if name == "alice"
+        );
 
+        cosm_orc.set_default_key(Some(key));
         let res = cosm_orc
-            .execute("cw_test", "e_test", msg, &key, vec![])
-            .unwrap()
-            .res;
-        assert_eq!(res.res.code, Code::Ok);
-        assert_eq!(res.res.data, Some(vec![]));
-        assert_eq!(res.res.log, "".to_string());
-        assert_eq!(res.gas_used, 100);
-        assert_eq!(res.gas_wanted, 101);
-
-        let report = cosm_orc.gas_profiler_report().unwrap();
-        assert_eq!(report.keys().len(), 1);
-        assert_eq!(report.get("cw_test").unwrap().keys().len(), 2);
-
-        let r = report
-            .get("cw_test")
-            .unwrap()
-            .get("Execute__e_test")
+            .signer()
+            .send(
+                "juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg",
+                "send",
+                amounts,
+            )
             .unwrap();
-        assert_eq!(r.gas_used, 100);
-        assert_eq!(r.gas_wanted, 101);
+        assert_eq!(res.res.tx_hash, "TX_HASH_0");
     }
 
     #[test]
-    fn query_not_stored() {
+    fn execute_without_key_uses_default_key() {
         let cfg = test_cfg();
-        let cosm_orc = CosmOrc {
-            contract_map: ContractMap::new(HashMap::new()),
-            client: CosmTome::new(cfg, MockCosmosClient::new()),
-            gas_profiler: None,
-            tx_options: TxOptions::default(),
-        };
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
 
-        let res = cosm_orc.query("cw_not_stored", &TestMsg {});
+        let mut mock_client = MockCosmosClient::new();
+        mock_tx_setup(&mut mock_client, 1);
+        mock_client
+            .expect_broadcast_tx_block()
+            .times(1)
+            .returning(|_| {
+                Ok(ChainTxResponse {
+                    res: ChainResponse {
+                        code: Code::Ok,
+                        data: None,
+                        log: "".to_string(),
+                    },
+                    events: vec![],
+                    gas_wanted: 101,
+                    gas_used: 100,
+                    tx_hash: "TX_HASH_0".to_string(),
+                    height: 1234,
+                })
+            });
+
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(HashMap::from([(
+                "cw_test".to_string(),
+                DeployInfo {
+                    code_id: Some(1337),
+                    address: Some("juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()),
+                },
+            )])),
+            ..test_cosm_orc(mock_client)
+        };
 
+        let res = cosm_orc.execute("cw_test", "e_test", &TestMsg {}, (), vec![]);
         assert_matches!(
             res.unwrap_err(),
-            ProcessError::ContractMapError(e) if e == ContractMapError::NotStored{name: "cw_not_stored".to_string()}
-        );
-
-        assert_eq!(
-            cosm_orc.contract_map.address("cw_not_stored").unwrap_err(),
-            ContractMapError::NotStored {
-                name: "cw_not_stored".to_string()
-            }
+            ProcessError::KeyError(KeyError::NoDefaultKey)
         );
 
-        assert_eq!(cosm_orc.gas_profiler_report(), None);
+        cosm_orc.set_default_key(Some(key));
+        let res = cosm_orc
+            .execute("cw_test", "e_test", &TestMsg {}, (), vec![])
+            .unwrap();
+        assert_eq!(res.res.tx_hash, "TX_HASH_0");
     }
 
     #[test]
-    fn query_not_initialized() {
+    fn submit_resyncs_on_sequence_mismatch() {
         let cfg = test_cfg();
-        let code_ids = HashMap::from([(
-            "cw_not_init".to_string(),
-            DeployInfo {
-                code_id: Some(1337),
-                address: None,
-            },
-        )]);
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let mut mock_client = MockCosmosClient::new();
+
+        let mut sequences = vec![5, 1];
+        mock_client
+            .expect_query::<QueryAccountRequest, QueryAccountResponse>()
+            .times(2)
+            .returning(move |_, t: &str| {
+                Ok(QueryAccountResponse {
+                    account: Some(cosmos_sdk_proto::Any {
+                        type_url: t.to_owned(),
+                        value: BaseAccount {
+                            address: "juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg".to_string(),
+                            pub_key: None,
+                            account_number: 1221,
+                            sequence: sequences.pop().unwrap(),
+                        }
+                        .to_bytes()
+                        .unwrap(),
+                    }),
+                })
+            });
+
+        mock_client.expect_simulate_tx().times(3).returning(|_| {
+            Ok(GasInfo {
+                gas_wanted: 200u16.into(),
+                gas_used: 100u16.into(),
+            })
+        });
+
+        let mut broadcasts = 0;
+        mock_client
+            .expect_broadcast_tx()
+            .times(3)
+            .returning(move |_, _| {
+                broadcasts += 1;
+                if broadcasts == 2 {
+                    return Err(ChainError::CosmosSdk {
+                        res: ChainResponse {
+                            code: Code::Err(32),
+                            data: None,
+                            log: "account sequence mismatch, expected 5, got 2: incorrect account sequence".to_string(),
+                        },
+                    });
+                }
+
+                Ok(AsyncChainTxResponse {
+                    res: ChainResponse {
+                        code: Code::Ok,
+                        data: Some(vec![]),
+                        log: "".to_string(),
+                    },
+                    tx_hash: format!("TX_HASH_{broadcasts}"),
+                })
+            });
 
-        let cosm_orc = CosmOrc {
-            contract_map: ContractMap::new(code_ids),
-            client: CosmTome::new(cfg, MockCosmosClient::new()),
-            gas_profiler: None,
-            tx_options: TxOptions::default(),
+        let mut cosm_orc = test_cosm_orc(mock_client);
+
+        let send = || SendReq {
+            to: "juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg".to_string(),
+            amounts: vec![Coin {
+                denom: "utest".parse().unwrap(),
+                amount: 100,
+            }],
         };
 
-        assert_eq!(
-            cosm_orc.contract_map.address("cw_not_init").unwrap_err(),
-            ContractMapError::NotDeployed {
-                name: "cw_not_init".to_string()
-            }
+        assert_matches!(
+            cosm_orc.submit_multi_send(vec![send()], &key).unwrap_err(),
+            ProcessError::SequenceManagerDisabled
         );
 
-        let res = cosm_orc.query("cw_not_init", &TestMsg {});
+        cosm_orc.set_sequence_manager(Some(SequenceManager::new()));
 
-        assert_matches!(
-            res.unwrap_err(),
-            ProcessError::ContractMapError(e) if e == ContractMapError::NotDeployed{name: "cw_not_init".to_string()}
-        );
+        let res = cosm_orc.submit_multi_send(vec![send()], &key).unwrap();
+        assert_eq!(res.tx_hash, "TX_HASH_1");
 
+        let res = cosm_orc.submit_multi_send(vec![send()], &key).unwrap();
+        assert_eq!(res.tx_hash, "TX_HASH_3");
+
+        let addr = tokio_block(key.to_addr(&cfg.prefix)).unwrap();
         assert_eq!(
-            cosm_orc.contract_map.address("cw_not_init").unwrap_err(),
-            ContractMapError::NotDeployed {
-                name: "cw_not_init".to_string()
-            }
+            cosm_orc.sequence_manager().unwrap().sequence(&addr),
+            Some(6)
         );
-
-        assert_eq!(cosm_orc.gas_profiler_report(), None);
     }
 
     #[test]
-    fn query_cosmossdk_error() {
+    fn load_test() {
         let cfg = test_cfg();
         let code_ids = HashMap::from([(
             "cw_test".to_string(),
             DeployInfo {
                 code_id: Some(1337),
-                address: None,
+                address: Some("juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()),
             },
         )]);
-        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
-
-        let msg = &TestMsg {};
+        // raw keys skip the slow mnemonic derivation of every tx
+        let keys = (1..=2)
+            .map(|i| SigningKey {
+                name: format!("key_{i}"),
+                key: Key::Raw(vec![i; 32]),
+                derivation_path: cfg.derivation_path.clone(),
+            })
+            .collect::<Vec<_>>();
 
         let mut mock_client = MockCosmosClient::new();
 
         mock_client
             .expect_query::<QueryAccountRequest, QueryAccountResponse>()
-            .times(1)
             .returning(move |_, t: &str| {
                 Ok(QueryAccountResponse {
                     account: Some(cosmos_sdk_proto::Any {
@@ -1455,106 +4092,228 @@ mod tests {
                 })
             });
 
-        mock_client.expect_simulate_tx().times(1).returning(|_| {
+        mock_client.expect_simulate_tx().returning(|_| {
             Ok(GasInfo {
                 gas_wanted: 200u16.into(),
                 gas_used: 100u16.into(),
             })
         });
 
+        let mut broadcasts = 0;
+        mock_client.expect_broadcast_tx_block().returning(move |_| {
+            broadcasts += 1;
+            if broadcasts % 2 == 0 {
+                return Err(ChainError::CosmosSdk {
+                    res: ChainResponse {
+                        code: Code::Err(11),
+                        data: None,
+                        log: "out of gas".to_string(),
+                    },
+                });
+            }
+
+            Ok(ChainTxResponse {
+                res: ChainResponse {
+                    code: Code::Ok,
+                    data: Some(vec![]),
+                    log: "".to_string(),
+                },
+                events: vec![],
+                gas_wanted: 101,
+                gas_used: 100,
+                tx_hash: "TX_HASH_0".to_string(),
+                height: 1234,
+            })
+        });
+
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(code_ids),
+            gas_profiler: Some(GasProfiler::new()),
+            ..test_cosm_orc(mock_client)
+        };
+
+        let load = LoadTest::new(keys, 20.0, Duration::from_millis(500));
+        let report = cosm_orc
+            .load_test("cw_test", "load", &load, |_, _| TestMsg {})
+            .unwrap();
+
+        assert!(report.sent > 0);
+        assert_eq!(report.sent, report.succeeded + report.failed);
+        assert_eq!(report.failures.get("out of gas"), Some(&report.failed));
+        assert_eq!(report.gas_used, report.succeeded * 100);
+        assert!(report.latency.p50 <= report.latency.max);
+
+        let report = cosm_orc.gas_profiler_report().unwrap();
+        assert!(report.get("cw_test").unwrap().contains_key("Execute__load"));
+
+        assert_matches!(
+            cosm_orc
+                .load_test("missing", "load", &load, |_, _| TestMsg {})
+                .unwrap_err(),
+            LoadTestError::ProcessError(ProcessError::ContractMapError(
+                ContractMapError::NotStored { .. }
+            ))
+        );
+    }
+
+    #[test]
+    fn execute_retries_transient_errors() {
+        let cfg = test_cfg();
+        let code_ids = HashMap::from([(
+            "cw_test".to_string(),
+            DeployInfo {
+                code_id: Some(1337),
+                address: Some("juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()),
+            },
+        )]);
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        // the unavailable endpoint gets the same tx again, the stale sequence is signed again
+        let mut mock_client = MockCosmosClient::new();
+        mock_tx_setup(&mut mock_client, 2);
+
+        let mut errors = vec![
+            || ChainError::CosmosSdk {
+                res: ChainResponse {
+                    code: Code::Err(32),
+                    data: None,
+                    log: "account sequence mismatch, expected 2, got 1: incorrect account sequence"
+                        .to_string(),
+                },
+            },
+            || {
+                ChainError::RPC(tendermint_rpc::Error::io(std::io::Error::from(
+                    std::io::ErrorKind::ConnectionRefused,
+                )))
+            },
+        ];
         mock_client
             .expect_broadcast_tx_block()
-            .times(1)
-            .returning(|_| {
-                Ok(ChainTxResponse {
+            .times(3)
+            .returning(move |_| match errors.pop() {
+                Some(e) => Err(e()),
+                None => Ok(ChainTxResponse {
                     res: ChainResponse {
                         code: Code::Ok,
                         data: Some(vec![]),
                         log: "".to_string(),
                     },
-                    events: vec![Event {
-                        type_str: "instantiate".to_string(),
-                        attributes: vec![Tag {
-                            key: "_contract_address".to_string(),
-                            value: "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string(),
-                        }],
-                    }],
+                    events: vec![],
                     gas_wanted: 101,
                     gas_used: 100,
                     tx_hash: "TX_HASH_0".to_string(),
                     height: 1234,
-                })
+                }),
             });
 
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(code_ids),
+            retry_policy: RetryPolicy {
+                max_attempts: 3,
+                backoff: Duration::from_millis(1),
+                ..Default::default()
+            },
+            ..test_cosm_orc(mock_client)
+        };
+
+        let res = cosm_orc
+            .execute("cw_test", "retry", &TestMsg {}, &key, vec![])
+            .unwrap();
+
+        assert_eq!(res.res.tx_hash, "TX_HASH_0");
+    }
+
+    #[test]
+    fn execute_timeout_looks_up_tx_without_signing_again() {
+        let cfg = test_cfg();
+        let code_ids = HashMap::from([(
+            "cw_test".to_string(),
+            DeployInfo {
+                code_id: Some(1337),
+                address: Some("juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()),
+            },
+        )]);
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let mut mock_client = MockCosmosClient::new();
+        mock_tx_setup(&mut mock_client, 1);
+
         mock_client
-            .expect_query::<QuerySmartContractStateRequest, QuerySmartContractStateResponse>()
+            .expect_broadcast_tx_block()
             .times(1)
-            .returning(move |_, _| {
-                Err(ChainError::CosmosSdk {
-                    res: ChainResponse {
-                        code: Code::Err(1),
-                        data: None,
-                        log: "error".to_string(),
-                    },
+            .returning(|_| {
+                Err(ChainError::RPC(tendermint_rpc::Error::timeout(
+                    Duration::from_secs(30),
+                )))
+            });
+
+        let mut lookups = 0;
+        mock_client
+            .expect_query::<GetTxRequest, GetTxResponse>()
+            .times(2)
+            .returning(move |req, _| {
+                lookups += 1;
+                if lookups == 1 {
+                    return Err(ChainError::CosmosSdk {
+                        res: ChainResponse {
+                            code: Code::Err(5),
+                            data: None,
+                            log: format!("tx not found: {}", req.hash),
+                        },
+                    });
+                }
+
+                Ok(GetTxResponse {
+                    tx: None,
+                    tx_response: Some(TxResponse {
+                        height: 1234,
+                        txhash: req.hash,
+                        gas_wanted: 101,
+                        gas_used: 100,
+                        ..Default::default()
+                    }),
                 })
             });
 
         let mut cosm_orc = CosmOrc {
             contract_map: ContractMap::new(code_ids),
-            client: CosmTome::new(cfg, mock_client),
-            gas_profiler: None,
-            tx_options: TxOptions::default(),
+            retry_policy: RetryPolicy {
+                max_attempts: 3,
+                backoff: Duration::from_millis(1),
+                retry_on: vec![RetryableError::Timeout],
+                ..Default::default()
+            },
+            ..test_cosm_orc(mock_client)
         };
 
-        assert_eq!(
-            cosm_orc.contract_map.address("cw_test").unwrap_err(),
-            ContractMapError::NotDeployed {
-                name: "cw_test".to_string()
-            }
-        );
-
         let res = cosm_orc
-            .instantiate("cw_test", "i_test", msg, &key, None, vec![])
-            .unwrap()
-            .res;
-
-        assert_eq!(res.res.code, Code::Ok);
-        assert_eq!(res.res.data, Some(vec![]));
-        assert_eq!(res.res.log, "".to_string());
-        assert_eq!(res.gas_used, 100);
-        assert_eq!(res.gas_wanted, 101);
-
-        assert_eq!(
-            cosm_orc.contract_map.address("cw_test").unwrap(),
-            "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()
-        );
-
-        let res = cosm_orc.query("cw_test", msg);
-
-        assert_matches!(res.unwrap_err(), ProcessError::CosmwasmError(..));
+            .execute("cw_test", "timeout", &TestMsg {}, &key, vec![])
+            .unwrap();
 
-        assert_eq!(cosm_orc.gas_profiler_report(), None);
+        assert_eq!(res.res.height, 1234);
+        assert_eq!(res.res.tx_hash.len(), 64);
     }
 
     #[test]
-    fn query() {
+    fn record_and_replay_transcript() {
         let cfg = test_cfg();
         let code_ids = HashMap::from([(
             "cw_test".to_string(),
             DeployInfo {
                 code_id: Some(1337),
-                address: None,
+                address: Some("juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()),
             },
         )]);
         let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
-
-        let msg = &TestMsg {};
+        let path =
+            std::env::temp_dir().join(format!("cosm-orc-replay-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
 
         let mut mock_client = MockCosmosClient::new();
 
         mock_client
             .expect_query::<QueryAccountRequest, QueryAccountResponse>()
-            .times(1)
+            .times(2)
             .returning(move |_, t: &str| {
                 Ok(QueryAccountResponse {
                     account: Some(cosmos_sdk_proto::Any {
@@ -1571,79 +4330,113 @@ mod tests {
                 })
             });
 
-        mock_client.expect_simulate_tx().times(1).returning(|_| {
+        mock_client.expect_simulate_tx().times(2).returning(|_| {
             Ok(GasInfo {
                 gas_wanted: 200u16.into(),
                 gas_used: 100u16.into(),
             })
         });
 
+        let mut heights = vec![20, 10];
         mock_client
             .expect_broadcast_tx_block()
-            .times(1)
-            .returning(|_| {
+            .times(2)
+            .returning(move |_| {
+                let height = heights.pop().unwrap();
                 Ok(ChainTxResponse {
                     res: ChainResponse {
                         code: Code::Ok,
                         data: Some(vec![]),
                         log: "".to_string(),
                     },
-                    events: vec![Event {
-                        type_str: "instantiate".to_string(),
-                        attributes: vec![Tag {
-                            key: "_contract_address".to_string(),
-                            value: "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string(),
-                        }],
-                    }],
+                    events: vec![],
                     gas_wanted: 101,
                     gas_used: 100,
-                    tx_hash: "TX_HASH_0".to_string(),
-                    height: 1234,
+                    tx_hash: format!("TX_HASH_{height}"),
+                    height,
                 })
             });
 
-        mock_client
-            .expect_query::<QuerySmartContractStateRequest, QuerySmartContractStateResponse>()
-            .times(1)
-            .returning(move |_, _| Ok(QuerySmartContractStateResponse { data: vec![] }));
-
         let mut cosm_orc = CosmOrc {
             contract_map: ContractMap::new(code_ids),
-            client: CosmTome::new(cfg, mock_client),
-            gas_profiler: None,
-            tx_options: TxOptions::default(),
+            transcript: Some(path.clone()),
+            ..test_cosm_orc(mock_client)
         };
 
-        assert_eq!(
-            cosm_orc.contract_map.address("cw_test").unwrap_err(),
-            ContractMapError::NotDeployed {
-                name: "cw_test".to_string()
-            }
+        cosm_orc
+            .execute("cw_test", "record", &TestMsg {}, &key, vec![])
+            .unwrap();
+
+        let recorded = transcript::read(&path).unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_matches!(
+            &recorded[0].op,
+            TranscriptOp::Execute { contract, msg, .. } if contract == "cw_test" && msg == &json!({})
         );
+        assert_eq!(recorded[0].key.as_deref(), Some("test"));
+        assert!(recorded[0].signer.is_some());
+        assert_eq!(recorded[0].result.as_ref().unwrap().tx_hash, "TX_HASH_10");
+
+        // replaying resolves `cw_test` through the contract map of the new chain
+        cosm_orc.set_transcript(None);
+        cosm_orc
+            .contract_map
+            .add_address("cw_test", "juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg")
+            .unwrap();
 
-        let res = cosm_orc
-            .instantiate("cw_test", "i_test", msg, &key, None, vec![])
-            .unwrap()
-            .res;
+        let replayed = cosm_orc.replay_transcript(&path, &key).unwrap();
+        assert_eq!(replayed.len(), 1);
+        assert_eq!(replayed[0].op, recorded[0].op);
+        assert_eq!(replayed[0].error, None);
+        assert_eq!(replayed[0].result.as_ref().unwrap().tx_hash, "TX_HASH_20");
 
-        assert_eq!(res.res.code, Code::Ok);
-        assert_eq!(res.res.data, Some(vec![]));
-        assert_eq!(res.res.log, "".to_string());
-        assert_eq!(res.gas_used, 100);
-        assert_eq!(res.gas_wanted, 101);
+        std::fs::remove_file(&path).unwrap();
+    }
 
-        assert_eq!(
-            cosm_orc.contract_map.address("cw_test").unwrap(),
-            "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()
-        );
-        assert_eq!(cosm_orc.gas_profiler_report(), None);
+    #[test]
+    fn execute_ignores_transcript_errors() {
+        let cfg = test_cfg();
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
 
-        let res = cosm_orc.query("cw_test", msg).unwrap();
-        assert_eq!(res.res.code, Code::Ok);
-        assert_eq!(res.res.data, Some(vec![]));
-        assert_eq!(res.res.log, "".to_string());
+        let mut mock_client = MockCosmosClient::new();
+        mock_tx_setup(&mut mock_client, 1);
+        mock_client
+            .expect_broadcast_tx_block()
+            .times(1)
+            .returning(|_| {
+                Ok(ChainTxResponse {
+                    res: ChainResponse {
+                        code: Code::Ok,
+                        data: None,
+                        log: "".to_string(),
+                    },
+                    events: vec![],
+                    gas_wanted: 101,
+                    gas_used: 100,
+                    tx_hash: "TX_HASH_0".to_string(),
+                    height: 1234,
+                })
+            });
 
-        assert_eq!(cosm_orc.gas_profiler_report(), None);
+        // a directory can't be appended to
+        let mut cosm_orc = CosmOrc {
+            contract_map: ContractMap::new(HashMap::from([(
+                "cw_test".to_string(),
+                DeployInfo {
+                    code_id: Some(1337),
+                    address: Some("juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string()),
+                },
+            )])),
+            transcript: Some(std::env::temp_dir()),
+            gas_profiler: Some(GasProfiler::new()),
+            ..test_cosm_orc(mock_client)
+        };
+
+        let res = cosm_orc
+            .execute("cw_test", "e_test", &TestMsg {}, &key, vec![])
+            .unwrap();
+        assert_eq!(res.res.tx_hash, "TX_HASH_0");
+        assert_eq!(cosm_orc.gas_profiler_report().unwrap().len(), 1);
     }
 
     #[test]
@@ -1654,15 +4447,50 @@ mod tests {
 
         let mut cosm_orc = CosmOrc {
             contract_map: ContractMap::new(code_ids),
-            client: CosmTome::new(cfg, MockCosmosClient::new()),
-            gas_profiler: None,
-            tx_options: TxOptions::default(),
+            ..test_cosm_orc(MockCosmosClient::new())
         };
 
         let res = cosm_orc.store_contracts("invalid_dir", &key, None);
         assert_matches!(res.unwrap_err(), StoreError::WasmDirRead { .. });
     }
 
+    #[test]
+    fn store_rejects_oversized_wasm() {
+        let cfg = test_cfg();
+        let key = SigningKey::random_mnemonic("test".to_string(), cfg.derivation_path.clone());
+
+        let dir = std::env::temp_dir().join(format!("cosm-orc-store-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("cw_test.wasm"),
+            wat::parse_str(
+                r#"(module (func (export "allocate") (param i32) (result i32) local.get 0))"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mut cosm_orc = CosmOrc {
+            store_policy: StorePolicy {
+                max_wasm_size: Some(8),
+                ..StorePolicy::default()
+            },
+            ..test_cosm_orc(MockCosmosClient::new())
+        };
+
+        let res = cosm_orc.store_contracts(dir.to_str().unwrap(), &key, None);
+        assert_matches!(
+            res.unwrap_err(),
+            StoreError::WasmTooLarge { contract, max_size: 8, .. } if contract == "cw_test"
+        );
+
+        std::fs::write(dir.join("cw_test.wasm"), b"not wasm").unwrap();
+        let res = cosm_orc.store_contracts(dir.to_str().unwrap(), &key, None);
+        assert_matches!(res.unwrap_err(), StoreError::InvalidWasm { .. });
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn migrate() {
         let cfg = test_cfg();
@@ -1733,9 +4561,7 @@ mod tests {
 
         let mut cosm_orc = CosmOrc {
             contract_map: ContractMap::new(code_ids),
-            client: CosmTome::new(cfg, mock_client),
-            gas_profiler: None,
-            tx_options: TxOptions::default(),
+            ..test_cosm_orc(mock_client)
         };
 
         let res = cosm_orc
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
 
 use super::error::ContractMapError;
 
@@ -79,6 +82,27 @@ impl ContractMap {
     pub fn deploy_info(&self) -> &HashMap<String, DeployInfo> {
         &self.map
     }
+
+    /// Writes the deploy info as json to `path`
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), io::Error> {
+        let json = serde_json::to_vec_pretty(&self.map)?;
+        fs::write(path, json)
+    }
+
+    /// Overrides the deploy info with the entries saved to `path` by [Self::save()].
+    /// Does nothing if `path` does not exist yet.
+    pub fn extend_from_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), io::Error> {
+        let json = match fs::read(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let saved: HashMap<ContractName, DeployInfo> = serde_json::from_slice(&json)?;
+        self.map.extend(saved);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -128,4 +152,27 @@ mod tests {
         assert_eq!(map.code_id("cw-test").unwrap(), 1337);
         assert_eq!(map.address("cw-test").unwrap(), "addr1");
     }
+
+    #[test]
+    fn save_and_load_state_file() {
+        let path = std::env::temp_dir().join("cosm_orc_contract_map_test.json");
+        let _ = std::fs::remove_file(&path);
+
+        let mut map = ContractMap::new(HashMap::new());
+        map.extend_from_file(&path).unwrap();
+        assert!(map.deploy_info().is_empty());
+
+        map.register_contract("cw-test", 1337);
+        map.add_address("cw-test", "addr1").unwrap();
+        map.save(&path).unwrap();
+
+        let mut loaded = ContractMap::new(HashMap::new());
+        loaded.register_contract("cw-other", 1);
+        loaded.extend_from_file(&path).unwrap();
+        assert_eq!(loaded.code_id("cw-test").unwrap(), 1337);
+        assert_eq!(loaded.address("cw-test").unwrap(), "addr1");
+        assert_eq!(loaded.code_id("cw-other").unwrap(), 1);
+
+        std::fs::remove_file(path).unwrap();
+    }
 }
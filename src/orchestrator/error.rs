@@ -1,5 +1,30 @@
+use cosm_tome::chain::response::ChainResponse;
 use thiserror::Error;
 
+use super::retry::AsChainError;
+use crate::config::error::{ConfigError, KeyError};
+
+#[derive(Error, Debug)]
+pub enum InitError {
+    #[error("config was not set")]
+    MissingConfig,
+
+    #[error(transparent)]
+    ConfigError(#[from] ConfigError),
+
+    #[error(transparent)]
+    KeyError(#[from] KeyError),
+
+    #[error(transparent)]
+    ChainError(#[from] ChainError),
+
+    #[error("error reading state file {path:?}")]
+    StateFile {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+}
+
 #[derive(Error, Debug)]
 pub enum StoreError {
     #[error("error reading wasm_dir")]
@@ -11,9 +36,55 @@ pub enum StoreError {
     #[error("wasm contract file name was not valid utf8 or malformed")]
     InvalidWasmFileName,
 
+    #[error("invalid artifact glob pattern {pattern:?}")]
+    InvalidGlob {
+        pattern: String,
+        source: globset::Error,
+    },
+
+    #[error("{contract} is not a valid wasm file")]
+    InvalidWasm {
+        contract: String,
+        source: wasmparser::BinaryReaderError,
+    },
+
+    #[error("{contract} wasm is {size} bytes, larger than the max size of {max_size} bytes")]
+    WasmTooLarge {
+        contract: String,
+        size: u64,
+        max_size: u64,
+    },
+
+    #[error("{contract} does not export an `interface_version_*` marker, it was not built as a CosmWasm contract")]
+    MissingInterfaceVersion { contract: String },
+
+    #[error("{contract} exports more than one `interface_version_*` marker: {versions:?}")]
+    MultipleInterfaceVersions {
+        contract: String,
+        versions: Vec<String>,
+    },
+
+    #[error("{contract} was built for unsupported CosmWasm interface version {version}")]
+    UnsupportedInterfaceVersion { contract: String, version: String },
+
+    #[error("{contract} is missing the required `{export}` export")]
+    MissingExport { contract: String, export: String },
+
+    #[error("{contract} requires capabilities not supported by the chain: {capabilities:?}")]
+    UnsupportedCapabilities {
+        contract: String,
+        capabilities: Vec<String>,
+    },
+
+    #[error("{contract} uses floats at offset {offset:#x}, which the chain rejects")]
+    FloatOperation { contract: String, offset: usize },
+
     #[error(transparent)]
     CosmwasmError(#[from] CosmwasmError),
 
+    #[error(transparent)]
+    KeyError(#[from] KeyError),
+
     #[error(transparent)]
     IOError(#[from] std::io::Error),
 }
@@ -39,14 +110,41 @@ pub enum ProcessError {
     #[error(transparent)]
     CosmwasmError(#[from] CosmwasmError),
 
+    #[error(transparent)]
+    BankError(#[from] BankError),
+
+    #[error(transparent)]
+    KeyError(#[from] KeyError),
+
     #[error(transparent)]
     IOError(#[from] std::io::Error),
+
+    #[error("sequence manager is not enabled")]
+    SequenceManagerDisabled,
+
+    /// Refunds of the listed test account addresses failed, the other accounts were refunded
+    #[error("failed to refund {} test account(s): {errors:?}", .errors.len())]
+    RefundFailed { errors: Vec<(String, BankError)> },
 }
 
 impl ProcessError {
     pub fn json(e: serde_json::Error) -> ProcessError {
         ProcessError::JsonSerialize { source: e }
     }
+
+    /// Returns the raw chain response if the chain rejected the tx or query
+    pub fn chain_response(&self) -> Option<&ChainResponse> {
+        let chain_err = match self {
+            ProcessError::CosmwasmError(e) => e.as_chain_error(),
+            ProcessError::BankError(e) => e.as_chain_error(),
+            _ => None,
+        }?;
+
+        match chain_err {
+            ChainError::CosmosSdk { res } => Some(res),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -58,6 +156,21 @@ pub enum ContractMapError {
     NotDeployed { name: String },
 }
 
+#[derive(Error, Debug)]
+pub enum EventError {
+    #[error("event not found in tx response: {event_type:?}")]
+    NotFound { event_type: String },
+
+    #[error("serde json deserialization error")]
+    JsonDeserialize { source: serde_json::Error },
+}
+
+impl EventError {
+    pub fn json(e: serde_json::Error) -> EventError {
+        EventError::JsonDeserialize { source: e }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum OptimizeError {
     #[error("error running optimizoor")]
@@ -71,12 +184,82 @@ pub enum PollBlockError {
     #[error(transparent)]
     Timeout(#[from] Elapsed),
 
+    #[error("block header missing from tendermint response")]
+    MissingBlockHeader,
+
+    #[error("block time missing from tendermint block header")]
+    MissingBlockTime,
+
     #[error(transparent)]
     TendermintError(#[from] TendermintError),
+
+    #[error(transparent)]
+    SubscribeError(#[from] SubscribeError),
+}
+
+#[derive(Error, Debug)]
+pub enum SubscribeError {
+    #[error("rpc_endpoint is required for websocket subscriptions")]
+    MissingRpcEndpoint,
+
+    #[error("invalid websocket endpoint: {endpoint:?}")]
+    InvalidEndpoint { endpoint: String },
+
+    #[error("websocket subscription closed")]
+    Closed,
+
+    #[error(transparent)]
+    ContractMapError(#[from] ContractMapError),
+
+    #[error("error starting websocket runtime")]
+    Runtime(#[source] std::io::Error),
+
+    #[error(transparent)]
+    Timeout(#[from] Elapsed),
+
+    #[error(transparent)]
+    RPC(#[from] TendermintRPCError),
+}
+
+#[derive(Error, Debug)]
+pub enum PollQueryError {
+    #[error(transparent)]
+    Timeout(#[from] Elapsed),
+
+    #[error(transparent)]
+    ProcessError(#[from] ProcessError),
+}
+
+#[derive(Error, Debug)]
+pub enum LoadTestError {
+    #[error("load test needs at least one key")]
+    NoKeys,
+
+    #[error("invalid load test rate: {rate:?} txs per second")]
+    InvalidRate { rate: f64 },
+
+    #[error(transparent)]
+    ProcessError(#[from] ProcessError),
+}
+
+#[derive(Error, Debug)]
+pub enum TranscriptError {
+    #[error("error reading transcript")]
+    IOError(#[from] std::io::Error),
+
+    #[error("invalid transcript entry on line {line}")]
+    InvalidEntry {
+        line: usize,
+        source: serde_json::Error,
+    },
+
+    #[error(transparent)]
+    KeyError(#[from] KeyError),
 }
 
-pub use cosm_tome::chain::error::ChainError;
+pub use cosm_tome::chain::error::{ChainError, TendermintRPCError};
 pub use cosm_tome::modules::auth::error::AccountError;
+pub use cosm_tome::modules::bank::error::BankError;
 pub use cosm_tome::modules::cosmwasm::error::CosmwasmError;
 pub use cosm_tome::modules::tendermint::error::TendermintError;
 pub use cosm_tome::modules::tx::error::TxError;
@@ -0,0 +1,287 @@
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use cosm_tome::chain::response::{ChainTxResponse, Event};
+use cosm_tome::modules::cosmwasm::model::{
+    ExecResponse, InstantiateResponse, MigrateResponse, StoreCodeResponse,
+};
+
+use super::error::EventError;
+use super::store::StoreResponse;
+use super::subscribe::TxEvent;
+
+/// Attribute key the wasm module uses to tag events with the emitting contract address
+pub const CONTRACT_ADDRESS_KEY: &str = "_contract_address";
+
+/// Helpers for reading a single tx event.
+pub trait EventExt {
+    /// Returns the value of the first attribute named `key`
+    fn attribute(&self, key: &str) -> Option<&str>;
+
+    /// Returns true for `wasm` events and custom `wasm-*` events emitted by contracts
+    fn is_wasm(&self) -> bool;
+
+    /// Returns the address of the contract that emitted this event
+    fn contract_address(&self) -> Option<&str>;
+
+    /// Deserializes the event attributes into `T`.
+    ///
+    /// Attribute values are always strings, so numeric fields need to be
+    /// declared as `String` (or use a `FromStr` based serde adapter).
+    fn deserialize<T: DeserializeOwned>(&self) -> Result<T, EventError>;
+}
+
+impl EventExt for Event {
+    fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|a| a.key == key)
+            .map(|a| a.value.as_str())
+    }
+
+    fn is_wasm(&self) -> bool {
+        self.type_str == "wasm" || self.type_str.starts_with("wasm-")
+    }
+
+    fn contract_address(&self) -> Option<&str> {
+        self.attribute(CONTRACT_ADDRESS_KEY)
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self) -> Result<T, EventError> {
+        let attrs = self
+            .attributes
+            .iter()
+            .map(|a| (a.key.clone(), Value::String(a.value.clone())))
+            .collect::<Map<_, _>>();
+
+        serde_json::from_value(Value::Object(attrs)).map_err(EventError::json)
+    }
+}
+
+/// Helpers for finding events in tx responses.
+pub trait TxEvents {
+    /// All events emitted by the tx
+    fn events(&self) -> &[Event];
+
+    /// Returns all events of type `event_type`
+    fn find_events(&self, event_type: &str) -> Vec<&Event> {
+        self.events()
+            .iter()
+            .filter(|e| e.type_str == event_type)
+            .collect()
+    }
+
+    /// Returns the value of the first `key` attribute in an `event_type` event
+    fn attribute(&self, event_type: &str, key: &str) -> Option<&str> {
+        self.find_events(event_type)
+            .into_iter()
+            .find_map(|e| e.attribute(key))
+    }
+
+    /// Returns the values of every `key` attribute in `event_type` events
+    fn attributes(&self, event_type: &str, key: &str) -> Vec<&str> {
+        self.find_events(event_type)
+            .into_iter()
+            .flat_map(|e| e.attributes.iter())
+            .filter(|a| a.key == key)
+            .map(|a| a.value.as_str())
+            .collect()
+    }
+
+    /// Returns the `wasm` and custom `wasm-*` events emitted by the contract at `contract_addr`
+    fn wasm_events(&self, contract_addr: &str) -> Vec<&Event> {
+        self.events()
+            .iter()
+            .filter(|e| e.is_wasm() && e.contract_address() == Some(contract_addr))
+            .collect()
+    }
+
+    /// Deserializes the attributes of the first `event_type` event into `T`
+    fn event_as<T: DeserializeOwned>(&self, event_type: &str) -> Result<T, EventError> {
+        self.find_events(event_type)
+            .first()
+            .ok_or_else(|| EventError::NotFound {
+                event_type: event_type.to_string(),
+            })?
+            .deserialize()
+    }
+
+    /// Deserializes the attributes of the first `wasm` event emitted by `contract_addr` into `T`
+    fn wasm_event_as<T: DeserializeOwned>(&self, contract_addr: &str) -> Result<T, EventError> {
+        self.wasm_events(contract_addr)
+            .into_iter()
+            .find(|e| e.type_str == "wasm")
+            .ok_or_else(|| EventError::NotFound {
+                event_type: "wasm".to_string(),
+            })?
+            .deserialize()
+    }
+}
+
+impl<T: TxEvents + ?Sized> TxEvents for &T {
+    fn events(&self) -> &[Event] {
+        (**self).events()
+    }
+}
+
+impl TxEvents for ChainTxResponse {
+    fn events(&self) -> &[Event] {
+        &self.events
+    }
+}
+
+impl TxEvents for TxEvent {
+    fn events(&self) -> &[Event] {
+        &self.events
+    }
+}
+
+impl TxEvents for StoreCodeResponse {
+    fn events(&self) -> &[Event] {
+        &self.res.events
+    }
+}
+
+impl TxEvents for StoreResponse {
+    fn events(&self) -> &[Event] {
+        &self.res.events
+    }
+}
+
+impl TxEvents for InstantiateResponse {
+    fn events(&self) -> &[Event] {
+        &self.res.events
+    }
+}
+
+impl TxEvents for ExecResponse {
+    fn events(&self) -> &[Event] {
+        &self.res.events
+    }
+}
+
+impl TxEvents for MigrateResponse {
+    fn events(&self) -> &[Event] {
+        &self.res.events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventExt, TxEvents};
+    use crate::orchestrator::error::EventError;
+    use assert_matches::assert_matches;
+    use cosm_tome::chain::response::{ChainTxResponse, Event, Tag};
+    use serde::Deserialize;
+
+    const CW20_ADDR: &str = "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj";
+
+    #[derive(Deserialize, Debug, PartialEq, Eq)]
+    struct Transfer {
+        action: String,
+        from: String,
+        to: String,
+        amount: String,
+    }
+
+    fn event(type_str: &str, attrs: &[(&str, &str)]) -> Event {
+        Event {
+            type_str: type_str.to_string(),
+            attributes: attrs
+                .iter()
+                .map(|(k, v)| Tag {
+                    key: k.to_string(),
+                    value: v.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    fn transfer_res() -> ChainTxResponse {
+        ChainTxResponse {
+            events: vec![
+                event("message", &[("action", "execute"), ("sender", "juno1a")]),
+                event(
+                    "execute",
+                    &[
+                        ("_contract_address", CW20_ADDR),
+                        ("_contract_address", "juno1c"),
+                    ],
+                ),
+                event(
+                    "wasm",
+                    &[
+                        ("_contract_address", CW20_ADDR),
+                        ("action", "transfer"),
+                        ("from", "juno1a"),
+                        ("to", "juno1b"),
+                        ("amount", "100"),
+                    ],
+                ),
+                event(
+                    "wasm",
+                    &[("_contract_address", "juno1c"), ("action", "hook")],
+                ),
+                event(
+                    "wasm-custom",
+                    &[("_contract_address", CW20_ADDR), ("key", "value")],
+                ),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn find_attributes() {
+        let res = transfer_res();
+
+        assert_eq!(res.find_events("wasm").len(), 2);
+        assert_eq!(res.attribute("wasm", "action"), Some("transfer"));
+        assert_eq!(res.attributes("wasm", "action"), vec!["transfer", "hook"]);
+        assert_eq!(
+            res.attributes("execute", "_contract_address"),
+            vec![CW20_ADDR, "juno1c"]
+        );
+        assert_eq!(res.attribute("wasm", "missing"), None);
+        assert_eq!(res.attribute("missing", "action"), None);
+    }
+
+    #[test]
+    fn filter_wasm_events_by_contract() {
+        let res = transfer_res();
+
+        let events = res.wasm_events(CW20_ADDR);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].attribute("action"), Some("transfer"));
+        assert_eq!(events[1].type_str, "wasm-custom");
+        assert_eq!(events[1].contract_address(), Some(CW20_ADDR));
+
+        assert_eq!(res.wasm_events("juno1c").len(), 1);
+        assert!(res.wasm_events("juno1d").is_empty());
+    }
+
+    #[test]
+    fn deserialize_wasm_event() {
+        let res = transfer_res();
+
+        let transfer: Transfer = res.wasm_event_as(CW20_ADDR).unwrap();
+        assert_eq!(
+            transfer,
+            Transfer {
+                action: "transfer".to_string(),
+                from: "juno1a".to_string(),
+                to: "juno1b".to_string(),
+                amount: "100".to_string(),
+            }
+        );
+
+        assert_matches!(
+            res.wasm_event_as::<Transfer>("juno1c").unwrap_err(),
+            EventError::JsonDeserialize { .. }
+        );
+        assert_matches!(
+            res.event_as::<Transfer>("transfer").unwrap_err(),
+            EventError::NotFound { event_type } if event_type == "transfer"
+        );
+    }
+}
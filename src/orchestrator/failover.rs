@@ -0,0 +1,323 @@
+use async_trait::async_trait;
+use prost::Message;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use cosm_tome::chain::error::ChainError;
+use cosm_tome::chain::fee::GasInfo;
+use cosm_tome::chain::response::{AsyncChainTxResponse, ChainTxResponse};
+use cosm_tome::clients::client::{CosmTome, CosmosClient};
+use cosm_tome::config::cfg::ChainConfig;
+use cosm_tome::modules::tx::model::{BroadcastMode, RawTx};
+
+use super::client::ChainClient;
+use super::retry::RetryableError;
+use crate::config::cfg::Endpoint;
+
+/// Shared handle to the endpoint currently used by a [FailoverClient].
+#[derive(Clone, Debug)]
+pub struct ActiveEndpoint {
+    urls: Arc<[String]>,
+    active: Arc<AtomicUsize>,
+}
+
+impl ActiveEndpoint {
+    pub(crate) fn new(urls: Vec<String>) -> Self {
+        Self {
+            urls: urls.into(),
+            active: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Url of the endpoint currently in use
+    pub fn url(&self) -> &str {
+        &self.urls[self.index()]
+    }
+
+    fn index(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    fn set(&self, i: usize) {
+        self.active.store(i, Ordering::Relaxed);
+    }
+}
+
+/// Cosmos client that switches to the next healthy endpoint when the current one is unreachable.
+///
+/// Endpoints are tried in order. Before failing over, the candidate is health checked with
+/// `tendermint_query_latest_block()`.
+///
+/// Queries and simulations fail over on timeouts and unreachable endpoints. Broadcasts only fail over
+/// when the endpoint was unreachable, since a timed out broadcast may still be committed.
+#[derive(Clone, Debug)]
+pub struct FailoverClient<C: CosmosClient = ChainClient> {
+    clients: Vec<CosmTome<C>>,
+    endpoint: ActiveEndpoint,
+}
+
+impl FailoverClient<ChainClient> {
+    pub fn new(cfg: &ChainConfig, endpoints: &[Endpoint]) -> Result<Self, ChainError> {
+        let clients = endpoints
+            .iter()
+            .map(|e| Ok((e.url().to_string(), ChainClient::from_endpoint(e)?)))
+            .collect::<Result<Vec<_>, ChainError>>()?;
+
+        Self::with_clients(cfg, clients)
+    }
+}
+
+impl<C: CosmosClient> FailoverClient<C> {
+    /// Fails over between `clients`, each paired with the url of its endpoint
+    pub fn with_clients(cfg: &ChainConfig, clients: Vec<(String, C)>) -> Result<Self, ChainError> {
+        if clients.is_empty() {
+            return Err(ChainError::MissingApiEndpoint {
+                api_type: "cosmos_grpc or tendermint_rpc".to_string(),
+            });
+        }
+
+        let (urls, clients) = clients
+            .into_iter()
+            .map(|(url, client)| (url, CosmTome::new(cfg.clone(), client)))
+            .unzip();
+
+        Ok(Self {
+            clients,
+            endpoint: ActiveEndpoint::new(urls),
+        })
+    }
+
+    /// Handle to the endpoint currently in use
+    pub fn active_endpoint(&self) -> &ActiveEndpoint {
+        &self.endpoint
+    }
+
+    /// Switches to the next healthy endpoint after `failed`, returning its index.
+    async fn fail_over(&self, failed: usize, e: ChainError) -> Result<usize, ChainError> {
+        warn!(
+            "endpoint {} is unreachable: {e}",
+            self.endpoint.urls[failed]
+        );
+
+        for offset in 1..self.clients.len() {
+            let i = (failed + offset) % self.clients.len();
+            let url = &self.endpoint.urls[i];
+
+            match self.clients[i].tendermint_query_latest_block().await {
+                Ok(_) => {
+                    warn!("failing over to endpoint {url}");
+                    self.endpoint.set(i);
+                    return Ok(i);
+                }
+                Err(e) => warn!("skipping unhealthy endpoint {url}: {e}"),
+            }
+        }
+
+        Err(e)
+    }
+}
+
+/// Returns true if `e` means the endpoint could not be reached or did not answer in time,
+/// decided from transport errors and gRPC status codes only
+fn is_connection_error(e: &ChainError) -> bool {
+    matches!(
+        RetryableError::classify(e),
+        Some(RetryableError::Unavailable | RetryableError::Timeout)
+    )
+}
+
+/// Returns true if `e` is a transport error meaning the endpoint could not be reached,
+/// so a broadcast tx was never sent. Failed txs never match, whatever their log says.
+fn is_unreachable(e: &ChainError) -> bool {
+    RetryableError::classify_broadcast(e) == Some(RetryableError::Unavailable)
+}
+
+#[async_trait]
+impl<C: CosmosClient + Send + Sync> CosmosClient for FailoverClient<C> {
+    async fn query<I, O>(&self, msg: I, path: &str) -> Result<O, ChainError>
+    where
+        Self: Sized,
+        I: Message + Default + tonic::IntoRequest<I> + 'static,
+        O: Message + Default + 'static,
+    {
+        // `I` is not `Clone`, so the request is re-decoded for every endpoint
+        let bytes = msg.encode_to_vec();
+        let mut i = self.endpoint.index();
+        let mut failovers = 0;
+
+        loop {
+            let msg = I::decode(bytes.as_slice()).map_err(|e| ChainError::ProtoDecoding {
+                message: e.to_string(),
+            })?;
+
+            match self.clients[i].client.query(msg, path).await {
+                Err(e) if is_connection_error(&e) && failovers < self.clients.len() => {
+                    failovers += 1;
+                    i = self.fail_over(i, e).await?;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    async fn simulate_tx(&self, tx: &RawTx) -> Result<GasInfo, ChainError> {
+        let mut i = self.endpoint.index();
+        let mut failovers = 0;
+
+        loop {
+            match self.clients[i].client.simulate_tx(tx).await {
+                Err(e) if is_connection_error(&e) && failovers < self.clients.len() => {
+                    failovers += 1;
+                    i = self.fail_over(i, e).await?;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    async fn broadcast_tx(
+        &self,
+        tx: &RawTx,
+        mode: BroadcastMode,
+    ) -> Result<AsyncChainTxResponse, ChainError> {
+        let mut i = self.endpoint.index();
+        let mut failovers = 0;
+
+        loop {
+            match self.clients[i].client.broadcast_tx(tx, mode).await {
+                Err(e) if is_unreachable(&e) && failovers < self.clients.len() => {
+                    failovers += 1;
+                    i = self.fail_over(i, e).await?;
+                }
+                Ok(res) => {
+                    info!(
+                        "tx {} broadcasted via {}",
+                        res.tx_hash, self.endpoint.urls[i]
+                    );
+                    return Ok(res);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn broadcast_tx_block(&self, tx: &RawTx) -> Result<ChainTxResponse, ChainError> {
+        let mut i = self.endpoint.index();
+        let mut failovers = 0;
+
+        loop {
+            match self.clients[i].client.broadcast_tx_block(tx).await {
+                Err(e) if is_unreachable(&e) && failovers < self.clients.len() => {
+                    failovers += 1;
+                    i = self.fail_over(i, e).await?;
+                }
+                Ok(res) => {
+                    info!("tx {} committed via {}", res.tx_hash, self.endpoint.urls[i]);
+                    return Ok(res);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FailoverClient;
+    use crate::orchestrator::cosm_orc::tokio_block;
+    use assert_matches::assert_matches;
+    use cosm_tome::chain::error::ChainError;
+    use cosm_tome::chain::response::{ChainResponse, Code};
+    use cosm_tome::clients::client::{CosmosClient, MockCosmosClient};
+    use cosm_tome::config::cfg::ChainConfig;
+    use cosm_tome::modules::tx::model::RawTx;
+    use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::{
+        GetLatestBlockRequest, GetLatestBlockResponse,
+    };
+    use std::time::Duration;
+
+    fn cfg() -> ChainConfig {
+        ChainConfig {
+            denom: "utest".to_string(),
+            prefix: "test".to_string(),
+            chain_id: "test-1".to_string(),
+            rpc_endpoint: None,
+            grpc_endpoint: Some("http://localhost:9090/".to_string()),
+            derivation_path: "m/44'/118'/0'/0/0".to_string(),
+            gas_price: 0.1,
+            gas_adjustment: 1.5,
+        }
+    }
+
+    #[test]
+    fn broadcast_timeout_does_not_fail_over() {
+        let mut first = MockCosmosClient::new();
+        first.expect_broadcast_tx_block().times(1).returning(|_| {
+            Err(ChainError::RPC(tendermint_rpc::Error::timeout(
+                Duration::from_secs(30),
+            )))
+        });
+
+        // the tx may have been committed by the first endpoint, so it is never sent again
+        let mut second = MockCosmosClient::new();
+        second.expect_broadcast_tx_block().never();
+
+        let client = FailoverClient::with_clients(
+            &cfg(),
+            vec![
+                ("http://node-1:9090/".to_string(), first),
+                ("http://node-2:9090/".to_string(), second),
+            ],
+        )
+        .unwrap();
+
+        let tx = RawTx::from_bytes(&[]).unwrap();
+        let res = tokio_block(client.broadcast_tx_block(&tx));
+
+        assert_matches!(res, Err(ChainError::RPC(_)));
+        assert_eq!(client.active_endpoint().url(), "http://node-1:9090/");
+    }
+
+    #[test]
+    fn failed_tx_does_not_fail_over() {
+        let mut first = MockCosmosClient::new();
+        first.expect_broadcast_tx_block().times(1).returning(|_| {
+            Err(ChainError::CosmosSdk {
+                res: ChainResponse {
+                    code: Code::Err(5),
+                    data: None,
+                    log: "failed to execute message; message index: 0: status 503: timeout"
+                        .to_string(),
+                },
+            })
+        });
+
+        let mut second = MockCosmosClient::new();
+        second.expect_broadcast_tx_block().never();
+        second
+            .expect_query::<GetLatestBlockRequest, GetLatestBlockResponse>()
+            .never();
+
+        let client = FailoverClient::with_clients(
+            &cfg(),
+            vec![
+                ("http://node-1:9090/".to_string(), first),
+                ("http://node-2:9090/".to_string(), second),
+            ],
+        )
+        .unwrap();
+
+        let tx = RawTx::from_bytes(&[]).unwrap();
+        let res = tokio_block(client.broadcast_tx_block(&tx));
+
+        assert_matches!(res, Err(ChainError::CosmosSdk { res }) if res.code == Code::Err(5));
+        assert_eq!(client.active_endpoint().url(), "http://node-1:9090/");
+    }
+
+    #[test]
+    fn requires_an_endpoint() {
+        let res = FailoverClient::<MockCosmosClient>::with_clients(&cfg(), vec![]);
+        assert_matches!(res, Err(ChainError::MissingApiEndpoint { .. }));
+    }
+}
@@ -1,8 +1,14 @@
+use cosm_tome::chain::coin::{Coin, Denom};
+use cosm_tome::chain::request::TxOptions;
 use cosm_tome::chain::response::ChainTxResponse;
+use cosm_tome::config::cfg::ChainConfig;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::panic::Location;
 
+use super::failover::ActiveEndpoint;
+use super::wasm::WasmInfo;
+
 #[derive(PartialEq, Eq, Debug)]
 pub enum CommandType {
     Store,
@@ -10,11 +16,17 @@ pub enum CommandType {
     Query,
     Execute,
     Migrate,
+    Send,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GasProfiler {
     report: Report,
+    #[serde(default)]
+    fees: FeeReport,
+    /// Endpoint recorded in each `GasReport`
+    #[serde(skip)]
+    endpoint: Option<ActiveEndpoint>,
 }
 
 pub type Report = HashMap<String, HashMap<String, GasReport>>;
@@ -25,6 +37,27 @@ pub struct GasReport {
     pub gas_used: u64,
     pub file_name: String,
     pub line_number: u32,
+    /// Endpoint that served the tx
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// Fee paid by the tx
+    #[serde(default)]
+    pub fee: Vec<Coin>,
+    /// Stored wasm artifact, for `CommandType::Store`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wasm: Option<WasmInfo>,
+}
+
+/// Total fees paid by the profiled txs.
+///
+/// Only successful txs are counted. A tx that failed in `DeliverTx` still pays its fee,
+/// so the totals are a lower bound of the funds spent when txs fail.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeeReport {
+    /// Fees per contract name, using the same keys as [Report]
+    pub per_contract: BTreeMap<String, Vec<Coin>>,
+    /// Fees per signer address
+    pub per_signer: BTreeMap<String, Vec<Coin>>,
 }
 
 impl Default for GasProfiler {
@@ -37,15 +70,25 @@ impl GasProfiler {
     pub fn new() -> Self {
         Self {
             report: HashMap::new(),
+            fees: FeeReport::default(),
+            endpoint: None,
         }
     }
 
+    /// Records the url of `endpoint` in every following `GasReport`
+    pub(crate) fn set_endpoint(&mut self, endpoint: Option<ActiveEndpoint>) {
+        self.endpoint = endpoint;
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn instrument(
         &mut self,
         contract: String,
         op_name: String,
         op_type: CommandType,
         response: &ChainTxResponse,
+        fee: &[Coin],
+        signer: Option<&str>,
         caller_loc: &Location,
     ) {
         if op_type == CommandType::Query {
@@ -57,6 +100,17 @@ impl GasProfiler {
         let caller_line_number = caller_loc.line();
         let op_key = format!("{op_type:?}__{op_name}");
 
+        add_coins(
+            self.fees.per_contract.entry(contract.clone()).or_default(),
+            fee,
+        );
+        if let Some(signer) = signer {
+            add_coins(
+                self.fees.per_signer.entry(signer.to_string()).or_default(),
+                fee,
+            );
+        }
+
         let m = self.report.entry(contract).or_default();
         m.insert(
             op_key,
@@ -65,11 +119,124 @@ impl GasProfiler {
                 gas_wanted: response.gas_wanted,
                 file_name: caller_file_name,
                 line_number: caller_line_number,
+                endpoint: self.endpoint.as_ref().map(|e| e.url().to_string()),
+                fee: fee.to_vec(),
+                wasm: None,
             },
         );
     }
 
+    /// Attaches `wasm` to the store report of `contract`
+    pub(crate) fn record_wasm(&mut self, contract: &str, wasm: WasmInfo) {
+        let op_key = format!("{:?}__Store", CommandType::Store);
+
+        if let Some(r) = self
+            .report
+            .get_mut(contract)
+            .and_then(|m| m.get_mut(&op_key))
+        {
+            r.wasm = Some(wasm);
+        }
+    }
+
     pub fn report(&self) -> &Report {
         &self.report
     }
+
+    pub fn fee_report(&self) -> &FeeReport {
+        &self.fees
+    }
+}
+
+/// Fee paid by a committed tx that wanted `gas_wanted` gas.
+///
+/// This is `TxOptions::fee` if it is set, otherwise the simulated fee of
+/// `gas_wanted * gas_price` in the configured denom.
+pub fn fee_paid(cfg: &ChainConfig, tx_options: &TxOptions, gas_wanted: u64) -> Vec<Coin> {
+    if let Some(fee) = &tx_options.fee {
+        return fee.amount.clone();
+    }
+
+    match cfg.denom.parse::<Denom>() {
+        Ok(denom) => vec![Coin {
+            denom,
+            amount: (gas_wanted as f64 * cfg.gas_price).ceil() as u128,
+        }],
+        Err(_) => vec![],
+    }
+}
+
+/// Adds `coins` to `total`, merging amounts of the same denom
+fn add_coins(total: &mut Vec<Coin>, coins: &[Coin]) {
+    for coin in coins {
+        match total.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(c) => c.amount += coin.amount,
+            None => total.push(coin.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fee_paid, CommandType, GasProfiler};
+    use crate::orchestrator::{Coin, Fee};
+    use cosm_tome::chain::request::TxOptions;
+    use cosm_tome::chain::response::ChainTxResponse;
+    use cosm_tome::config::cfg::ChainConfig;
+    use std::panic::Location;
+
+    fn ujunox(amount: u128) -> Coin {
+        Coin {
+            denom: "ujunox".parse().unwrap(),
+            amount,
+        }
+    }
+
+    #[test]
+    fn fee_from_gas_price() {
+        let cfg = ChainConfig {
+            denom: "ujunox".to_string(),
+            prefix: "juno".to_string(),
+            chain_id: "testing".to_string(),
+            rpc_endpoint: None,
+            grpc_endpoint: Some("http://localhost:9090/".to_string()),
+            derivation_path: "m/44'/118'/0'/0/0".to_string(),
+            gas_price: 0.1,
+            gas_adjustment: 1.5,
+        };
+        assert_eq!(fee_paid(&cfg, &TxOptions::default(), 101), vec![ujunox(11)]);
+
+        let tx_options = TxOptions {
+            fee: Some(Fee::new(ujunox(5000), 200_000u64, None, None)),
+            ..Default::default()
+        };
+        assert_eq!(fee_paid(&cfg, &tx_options, 101), vec![ujunox(5000)]);
+    }
+
+    #[test]
+    fn fee_totals() {
+        let mut profiler = GasProfiler::new();
+        let res = ChainTxResponse::default();
+
+        for (op_name, signer) in [("a", "juno1alice"), ("b", "juno1alice"), ("c", "juno1bob")] {
+            profiler.instrument(
+                "cw20".to_string(),
+                op_name.to_string(),
+                CommandType::Execute,
+                &res,
+                &[ujunox(10)],
+                Some(signer),
+                Location::caller(),
+            );
+        }
+
+        let fees = profiler.fee_report();
+        assert_eq!(fees.per_contract["cw20"], vec![ujunox(30)]);
+        assert_eq!(fees.per_signer["juno1alice"], vec![ujunox(20)]);
+        assert_eq!(fees.per_signer["juno1bob"], vec![ujunox(10)]);
+        assert_eq!(
+            profiler.report()["cw20"]["Execute__a"].fee,
+            vec![ujunox(10)]
+        );
+    }
 }
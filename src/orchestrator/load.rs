@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use cosm_tome::chain::response::ChainTxResponse;
+use cosm_tome::signing_key::key::SigningKey;
+
+use super::error::{LoadTestError, ProcessError};
+
+/// Load generated by [CosmOrc::load_test()](crate::orchestrator::cosm_orc::CosmOrc::load_test).
+///
+/// Every key has at most one tx in flight, so the achievable rate is bounded by
+/// `keys.len() / block_time`. Ticks where every key is still waiting for its previous tx
+/// are counted as `skipped`.
+#[derive(Clone, Debug)]
+pub struct LoadTest {
+    /// Keys signing the txs, in round robin order
+    pub keys: Vec<SigningKey>,
+    /// Target txs per second
+    pub rate: f64,
+    /// How long txs are fired for. In flight txs are still awaited afterwards.
+    pub duration: Duration,
+}
+
+impl LoadTest {
+    pub fn new(keys: Vec<SigningKey>, rate: f64, duration: Duration) -> Self {
+        Self {
+            keys,
+            rate,
+            duration,
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), LoadTestError> {
+        if self.keys.is_empty() {
+            return Err(LoadTestError::NoKeys);
+        }
+
+        self.interval()?;
+
+        Ok(())
+    }
+
+    /// Time between two fired txs, rates whose interval doesn't fit a non zero `Duration`
+    /// are rejected
+    pub(crate) fn interval(&self) -> Result<Duration, LoadTestError> {
+        if !self.rate.is_finite() || self.rate <= 0.0 {
+            return Err(LoadTestError::InvalidRate { rate: self.rate });
+        }
+
+        Duration::try_from_secs_f64(1.0 / self.rate)
+            .ok()
+            .filter(|interval| !interval.is_zero())
+            .ok_or(LoadTestError::InvalidRate { rate: self.rate })
+    }
+}
+
+/// Results of a [LoadTest]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct LoadReport {
+    /// Txs broadcasted
+    pub sent: u64,
+    /// Txs committed successfully
+    pub succeeded: u64,
+    /// Txs that failed, see `failures` for the reasons
+    pub failed: u64,
+    /// Ticks without an idle key to sign with
+    pub skipped: u64,
+    /// Wall time of the load test, including waiting for in flight txs
+    pub elapsed: Duration,
+    /// Committed txs per second
+    pub tps: f64,
+    /// Time between broadcasting a tx and its inclusion in a block
+    pub latency: LatencyReport,
+    /// Number of failed txs per error message
+    pub failures: BTreeMap<String, u64>,
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LatencyReport {
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+/// Accumulates tx results while the load test runs
+#[derive(Debug, Default)]
+pub(crate) struct LoadStats {
+    pub(crate) sent: u64,
+    pub(crate) skipped: u64,
+    failed: u64,
+    latencies: Vec<Duration>,
+    failures: BTreeMap<String, u64>,
+    gas_wanted: u64,
+    gas_used: u64,
+}
+
+impl LoadStats {
+    pub(crate) fn success(&mut self, latency: Duration, res: &ChainTxResponse) {
+        self.latencies.push(latency);
+        self.gas_wanted += res.gas_wanted;
+        self.gas_used += res.gas_used;
+    }
+
+    pub(crate) fn failure(&mut self, e: &ProcessError) {
+        let reason = match e.chain_response() {
+            Some(res) => res.log.clone(),
+            None => e.to_string(),
+        };
+
+        self.failed += 1;
+        *self.failures.entry(reason).or_default() += 1;
+    }
+
+    pub(crate) fn finish(mut self, elapsed: Duration) -> LoadReport {
+        self.latencies.sort();
+
+        let succeeded = self.latencies.len() as u64;
+
+        LoadReport {
+            sent: self.sent,
+            succeeded,
+            failed: self.failed,
+            skipped: self.skipped,
+            elapsed,
+            tps: succeeded as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+            latency: LatencyReport {
+                p50: percentile(&self.latencies, 50.0),
+                p90: percentile(&self.latencies, 90.0),
+                p99: percentile(&self.latencies, 99.0),
+                max: self.latencies.last().copied().unwrap_or_default(),
+            },
+            failures: self.failures,
+            gas_wanted: self.gas_wanted,
+            gas_used: self.gas_used,
+        }
+    }
+}
+
+/// Nearest rank percentile of already sorted `values`
+fn percentile(values: &[Duration], p: f64) -> Duration {
+    if values.is_empty() {
+        return Duration::ZERO;
+    }
+
+    let rank = (p / 100.0 * values.len() as f64).ceil() as usize;
+    values[rank.clamp(1, values.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{percentile, LoadTest};
+    use crate::orchestrator::error::LoadTestError;
+    use assert_matches::assert_matches;
+    use cosm_tome::signing_key::key::SigningKey;
+    use std::time::Duration;
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let values = (1..=10).map(Duration::from_millis).collect::<Vec<_>>();
+
+        assert_eq!(percentile(&values, 50.0), Duration::from_millis(5));
+        assert_eq!(percentile(&values, 90.0), Duration::from_millis(9));
+        assert_eq!(percentile(&values, 99.0), Duration::from_millis(10));
+        assert_eq!(percentile(&[], 50.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn validate_load_test() {
+        assert_matches!(
+            LoadTest::new(vec![], 10.0, Duration::from_secs(1)).validate(),
+            Err(LoadTestError::NoKeys)
+        );
+
+        let key = SigningKey::random_mnemonic("test".to_string(), "m/44'/118'/0'/0/0".to_string());
+        for rate in [0.0, -1.0, f64::NAN, 1e-300, 1e300] {
+            assert_matches!(
+                LoadTest::new(vec![key.clone()], rate, Duration::from_secs(1)).validate(),
+                Err(LoadTestError::InvalidRate { .. })
+            );
+        }
+
+        let load = LoadTest::new(vec![key], 4.0, Duration::from_secs(1));
+        assert_eq!(load.interval().unwrap(), Duration::from_millis(250));
+    }
+}
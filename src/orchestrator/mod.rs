@@ -1,11 +1,41 @@
+pub mod accounts;
+
+pub mod builder;
+
+pub mod client;
+
 pub mod cosm_orc;
 
 pub mod deploy;
 
 pub mod error;
 
+pub mod events;
+
+pub mod failover;
+
 pub mod gas_profiler;
 
+pub mod load;
+
+pub mod poll;
+
+pub mod retry;
+
+pub mod sequence;
+
+pub mod signer;
+
+pub mod store;
+
+pub mod subscribe;
+
+mod trace;
+
+pub mod transcript;
+
+pub mod wasm;
+
 /// Batch wasm execute request
 pub struct ExecReq {
     /// Deployed smart contract name for the corresponding `msg`
@@ -16,12 +46,57 @@ pub struct ExecReq {
     pub funds: Vec<Coin>,
 }
 
+/// Bank send request
+pub struct SendReq {
+    /// Recipient address, or the name of a deployed smart contract
+    pub to: String,
+    /// Tokens to send to `to`
+    pub amounts: Vec<Coin>,
+}
+
+/// Key used to sign a tx: either a `SigningKey`, the name of a key configured in `Config::keys`,
+/// or `()` for the default key of the `CosmOrc`
+#[derive(Clone, Copy, Debug)]
+pub enum KeyRef<'a> {
+    Key(&'a SigningKey),
+    Name(&'a str),
+    /// The default key of the `CosmOrc`
+    Default,
+}
+
+impl<'a> From<&'a SigningKey> for KeyRef<'a> {
+    fn from(key: &'a SigningKey) -> Self {
+        KeyRef::Key(key)
+    }
+}
+
+impl<'a> From<&'a str> for KeyRef<'a> {
+    fn from(name: &'a str) -> Self {
+        KeyRef::Name(name)
+    }
+}
+
+impl<'a> From<&'a String> for KeyRef<'a> {
+    fn from(name: &'a String) -> Self {
+        KeyRef::Name(name)
+    }
+}
+
+/// Lets operations be called without naming a key, eg. `cosm_orc.execute(.., (), vec![])`
+impl From<()> for KeyRef<'_> {
+    fn from(_: ()) -> Self {
+        KeyRef::Default
+    }
+}
+
+pub use client::ChainClient;
 pub use cosm_tome::chain::coin::{Coin, Denom};
 pub use cosm_tome::chain::fee::{Fee, Gas};
-pub use cosm_tome::chain::response::{ChainResponse, ChainTxResponse, Code};
+pub use cosm_tome::chain::response::{ChainResponse, ChainTxResponse, Code, Event, Tag};
 pub use cosm_tome::clients::cosmos_grpc::CosmosgRPC;
 pub use cosm_tome::clients::tendermint_rpc::TendermintRPC;
 pub use cosm_tome::modules::auth::model::Address;
+pub use cosm_tome::modules::bank::model::SendResponse;
 pub use cosm_tome::modules::cosmwasm::model::{AccessConfig, AccessType};
 pub use cosm_tome::modules::cosmwasm::model::{
     ExecResponse, InstantiateResponse, MigrateResponse, QueryResponse, StoreCodeResponse,
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+/// Controls how often the chain is queried while polling for new blocks.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PollPolicy {
+    /// Time to wait before the first re-query.
+    pub interval: Duration,
+    /// Multiplier applied to the wait time after each query. `1.0` disables backoff.
+    pub backoff: f64,
+    /// Upper bound for every wait time, including `interval` and `jitter`.
+    pub max_interval: Duration,
+    /// Maximum random duration added on top of each wait.
+    pub jitter: Duration,
+    /// Number of consecutive failed queries tolerated before the error is returned.
+    pub max_errors: u32,
+    /// Wait for `NewBlock` events over the tendermint websocket instead of querying the latest block.
+    /// Requires `rpc_endpoint` to be configured.
+    #[serde(default)]
+    pub subscribe: bool,
+}
+
+impl Default for PollPolicy {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(500),
+            backoff: 1.0,
+            max_interval: Duration::from_secs(10),
+            jitter: Duration::ZERO,
+            max_errors: 0,
+            subscribe: false,
+        }
+    }
+}
+
+impl PollPolicy {
+    pub(crate) fn poller(&self) -> Poller<'_> {
+        Poller {
+            policy: self,
+            delay: self.interval,
+            errors: 0,
+        }
+    }
+}
+
+/// Tracks the wait time and error count of a single polling loop.
+pub(crate) struct Poller<'a> {
+    policy: &'a PollPolicy,
+    delay: Duration,
+    errors: u32,
+}
+
+impl Poller<'_> {
+    /// Returns the time to wait before the next query and applies backoff for the one after.
+    pub(crate) fn next_delay(&mut self) -> Duration {
+        let delay = (self.delay + jitter(self.policy.jitter)).min(self.policy.max_interval);
+
+        let next = self.delay.as_secs_f64() * self.policy.backoff.max(1.0);
+        self.delay = Duration::from_secs_f64(next.min(self.policy.max_interval.as_secs_f64()));
+
+        delay
+    }
+
+    /// Returns `e` once more than `max_errors` consecutive errors have been recorded.
+    pub(crate) fn record_error<E: Display>(&mut self, e: E) -> Result<(), E> {
+        self.errors += 1;
+        if self.errors > self.policy.max_errors {
+            return Err(e);
+        }
+
+        warn!(
+            "poll query failed ({}/{}): {}",
+            self.errors, self.policy.max_errors, e
+        );
+        Ok(())
+    }
+
+    pub(crate) fn reset_errors(&mut self) {
+        self.errors = 0;
+    }
+}
+
+fn jitter(max: Duration) -> Duration {
+    if max.is_zero() {
+        return Duration::ZERO;
+    }
+
+    // cheap pseudo random source, good enough to spread out concurrent pollers
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos() as u128;
+
+    Duration::from_nanos((nanos % (max.as_nanos() + 1)) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PollPolicy;
+    use std::time::Duration;
+
+    #[test]
+    fn default_policy_has_constant_interval() {
+        let policy = PollPolicy::default();
+        let mut poller = policy.poller();
+
+        for _ in 0..5 {
+            assert_eq!(poller.next_delay(), Duration::from_millis(500));
+        }
+    }
+
+    #[test]
+    fn backoff_is_capped_at_max_interval() {
+        let policy = PollPolicy {
+            interval: Duration::from_millis(100),
+            backoff: 2.0,
+            max_interval: Duration::from_millis(500),
+            ..Default::default()
+        };
+        let mut poller = policy.poller();
+
+        assert_eq!(poller.next_delay(), Duration::from_millis(100));
+        assert_eq!(poller.next_delay(), Duration::from_millis(200));
+        assert_eq!(poller.next_delay(), Duration::from_millis(400));
+        assert_eq!(poller.next_delay(), Duration::from_millis(500));
+        assert_eq!(poller.next_delay(), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn every_delay_is_capped_at_max_interval() {
+        let policy = PollPolicy {
+            interval: Duration::from_millis(800),
+            backoff: 2.0,
+            max_interval: Duration::from_millis(500),
+            jitter: Duration::from_millis(50),
+            ..Default::default()
+        };
+        let mut poller = policy.poller();
+
+        for _ in 0..5 {
+            assert_eq!(poller.next_delay(), Duration::from_millis(500));
+        }
+    }
+
+    #[test]
+    fn jitter_stays_in_bounds() {
+        let policy = PollPolicy {
+            interval: Duration::from_millis(100),
+            jitter: Duration::from_millis(50),
+            ..Default::default()
+        };
+        let mut poller = policy.poller();
+
+        for _ in 0..20 {
+            let delay = poller.next_delay();
+            assert!(delay >= Duration::from_millis(100));
+            assert!(delay <= Duration::from_millis(150));
+        }
+    }
+
+    #[test]
+    fn tolerates_max_errors() {
+        let policy = PollPolicy {
+            max_errors: 2,
+            ..Default::default()
+        };
+        let mut poller = policy.poller();
+
+        assert!(poller.record_error("err").is_ok());
+        assert!(poller.record_error("err").is_ok());
+        assert_eq!(poller.record_error("err"), Err("err"));
+
+        poller.reset_errors();
+        assert!(poller.record_error("err").is_ok());
+    }
+}
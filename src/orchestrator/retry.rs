@@ -0,0 +1,452 @@
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{GetTxRequest, GetTxResponse};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+use tendermint_rpc::error::ErrorDetail;
+use tokio::time;
+use tracing::{debug, warn};
+
+use cosm_tome::chain::error::ChainError;
+use cosm_tome::chain::msg::Msg;
+use cosm_tome::chain::request::TxOptions;
+use cosm_tome::chain::response::ChainTxResponse;
+use cosm_tome::clients::client::{CosmTome, CosmosClient};
+use cosm_tome::modules::auth::error::AccountError;
+use cosm_tome::modules::auth::model::Address;
+use cosm_tome::modules::bank::error::BankError;
+use cosm_tome::modules::cosmwasm::error::CosmwasmError;
+use cosm_tome::modules::tx::error::TxError;
+use cosm_tome::modules::tx::model::RawTx;
+use cosm_tome::signing_key::key::SigningKey;
+
+/// ABCI code of a tx rejected with `tx already in mempool` or `tx already exists in cache`
+const CODE_TX_IN_MEMPOOL: u32 = 19;
+
+/// ABCI code of a tx rejected with `account sequence mismatch`
+const CODE_SEQUENCE_MISMATCH: u32 = 32;
+
+/// Transient failure that a [RetryPolicy] can retry.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryableError {
+    /// Transport timeouts and gRPC `DeadlineExceeded` statuses
+    Timeout,
+    /// Unreachable endpoints, transport errors and gRPC `Unavailable` statuses
+    Unavailable,
+    /// `tx already in mempool`, ABCI code 19
+    TxInMempool,
+    /// `account sequence mismatch`, ABCI code 32
+    SequenceMismatch,
+}
+
+impl RetryableError {
+    /// Returns the class of a failed query or simulation, or None if it is not a transient failure.
+    ///
+    /// Besides the failures of [Self::classify_broadcast()], gRPC `Unavailable` and
+    /// `DeadlineExceeded` statuses are transient. Their codes overlap ABCI codes,
+    /// so they are only trusted for requests that don't broadcast a tx.
+    pub fn classify(e: &ChainError) -> Option<RetryableError> {
+        if let ChainError::CosmosSdk { res } = e {
+            match res.code.value() {
+                c if c == tonic::Code::Unavailable as u32 => {
+                    return Some(RetryableError::Unavailable)
+                }
+                c if c == tonic::Code::DeadlineExceeded as u32 => {
+                    return Some(RetryableError::Timeout)
+                }
+                _ => {}
+            }
+        }
+
+        Self::classify_broadcast(e)
+    }
+
+    /// Returns the class of a failed broadcast, or None if it is not a transient failure.
+    ///
+    /// Only transport errors and txs rejected by `CheckTx` with ABCI code 19 or 32 are transient,
+    /// the log of a failed tx is never inspected.
+    pub fn classify_broadcast(e: &ChainError) -> Option<RetryableError> {
+        match e {
+            ChainError::CosmosSdk { res } => match res.code.value() {
+                CODE_SEQUENCE_MISMATCH => Some(RetryableError::SequenceMismatch),
+                CODE_TX_IN_MEMPOOL => Some(RetryableError::TxInMempool),
+                _ => None,
+            },
+            ChainError::GRPC(_) => Some(RetryableError::Unavailable),
+            ChainError::RPC(e) => match e.detail() {
+                ErrorDetail::Timeout(_) | ErrorDetail::WebSocketTimeout(_) => {
+                    Some(RetryableError::Timeout)
+                }
+                ErrorDetail::Io(_)
+                | ErrorDetail::Http(_)
+                | ErrorDetail::Hyper(_)
+                | ErrorDetail::WebSocket(_)
+                | ErrorDetail::ChannelSend(_) => Some(RetryableError::Unavailable),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Controls how chain operations are retried after transient failures.
+///
+/// Retries are disabled by default, set `max_attempts` to enable them.
+///
+/// Txs are only signed again after failures raised before they reach the mempool.
+/// A tx whose broadcast failed with [RetryableError::Timeout] or [RetryableError::TxInMempool]
+/// may still be committed, so it is looked up by hash instead, see [Self::broadcast()].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    /// Time to wait before the first retry.
+    pub backoff: Duration,
+    /// Multiplier applied to the wait time after each retry. `1.0` disables backoff.
+    pub multiplier: f64,
+    /// Upper bound for the wait time once `multiplier` has been applied.
+    pub max_backoff: Duration,
+    /// Failures that are retried
+    pub retry_on: Vec<RetryableError>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            backoff: Duration::from_secs(1),
+            multiplier: 2.0,
+            max_backoff: Duration::from_secs(30),
+            retry_on: vec![
+                RetryableError::Unavailable,
+                RetryableError::SequenceMismatch,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns true if `e` is a failure listed in `retry_on`
+    pub fn is_retryable(&self, e: &ChainError) -> bool {
+        RetryableError::classify(e).is_some_and(|class| self.retry_on.contains(&class))
+    }
+
+    /// Returns the class of `e` if it is a failure listed in `retry_on`
+    fn retry_class(&self, e: &impl AsChainError) -> Option<RetryableError> {
+        e.as_chain_error()
+            .and_then(RetryableError::classify)
+            .filter(|class| self.retry_on.contains(class))
+    }
+
+    /// Same as [Self::retry_class()] for a failed broadcast, see [RetryableError::classify_broadcast()]
+    fn broadcast_retry_class(&self, e: &TxError) -> Option<RetryableError> {
+        e.as_chain_error()
+            .and_then(RetryableError::classify_broadcast)
+            .filter(|class| self.retry_on.contains(class))
+    }
+
+    fn attempts(&self) -> Attempts<'_> {
+        Attempts {
+            policy: self,
+            attempt: 1,
+            delay: self.backoff,
+        }
+    }
+
+    /// Runs `f` until it succeeds, fails with a non retryable error or runs out of attempts.
+    pub(crate) async fn run<T, E, F, Fut>(&self, op_name: &str, mut f: F) -> Result<T, E>
+    where
+        E: AsChainError + Display,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut attempts = self.attempts();
+
+        loop {
+            match f().await {
+                Err(e) if self.retry_class(&e).is_some() => {
+                    if !attempts.next(op_name, &e).await {
+                        return Err(e);
+                    }
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// Signs `msgs` with `key` and broadcasts them, waiting for the tx to be committed.
+    ///
+    /// Failures while signing are retried, as is a tx rejected with an `account sequence mismatch`,
+    /// since it never entered the mempool. A tx sent to an unavailable endpoint is broadcast
+    /// again unchanged. After a `Timeout` or `TxInMempool` failure the tx is never signed again,
+    /// it is looked up by hash until it is found or the attempts run out.
+    pub(crate) async fn broadcast<C, M>(
+        &self,
+        op_name: &str,
+        client: &CosmTome<C>,
+        msgs: Vec<M>,
+        sender: &Address,
+        key: &SigningKey,
+        tx_options: &TxOptions,
+    ) -> Result<ChainTxResponse, TxError>
+    where
+        C: CosmosClient,
+        M: Msg + Serialize,
+    {
+        let mut attempts = self.attempts();
+        let mut signed = None;
+
+        loop {
+            let tx = match signed.take() {
+                Some(tx) => tx,
+                None => match client
+                    .tx_sign(msgs.clone(), Some(sender.clone()), key, tx_options)
+                    .await
+                {
+                    Ok(tx) => tx,
+                    Err(e) if self.retry_class(&e).is_some() => {
+                        if !attempts.next(op_name, &e).await {
+                            return Err(e);
+                        }
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                },
+            };
+
+            let e = match client.tx_broadcast_block(&tx).await {
+                Ok(res) => return Ok(res),
+                Err(e) => e,
+            };
+
+            match self.broadcast_retry_class(&e) {
+                Some(RetryableError::Unavailable) => signed = Some(tx),
+                Some(RetryableError::SequenceMismatch) => {}
+                Some(RetryableError::Timeout | RetryableError::TxInMempool) => {
+                    return find_tx(op_name, client, &tx, e, attempts).await;
+                }
+                None => return Err(e),
+            }
+
+            if !attempts.next(op_name, &e).await {
+                return Err(e);
+            }
+        }
+    }
+}
+
+/// Attempts made so far by a [RetryPolicy]
+struct Attempts<'a> {
+    policy: &'a RetryPolicy,
+    attempt: u32,
+    delay: Duration,
+}
+
+impl Attempts<'_> {
+    /// Waits before the next attempt, returns false if every attempt has been used
+    async fn next(&mut self, op_name: &str, e: &impl Display) -> bool {
+        if self.attempt >= self.policy.max_attempts {
+            return false;
+        }
+
+        warn!(
+            "{op_name} failed (attempt {}/{}), retrying in {:?}: {e}",
+            self.attempt, self.policy.max_attempts, self.delay
+        );
+
+        time::sleep(self.delay).await;
+
+        self.attempt += 1;
+        let next = self.delay.as_secs_f64() * self.policy.multiplier.max(1.0);
+        self.delay = Duration::from_secs_f64(next.min(self.policy.max_backoff.as_secs_f64()));
+
+        true
+    }
+}
+
+/// Looks up `tx` after its broadcast failed with `e`, returning `e` if it is not found
+/// before the attempts run out
+async fn find_tx<C: CosmosClient>(
+    op_name: &str,
+    client: &CosmTome<C>,
+    tx: &RawTx,
+    e: TxError,
+    mut attempts: Attempts<'_>,
+) -> Result<ChainTxResponse, TxError> {
+    let hash = tx_hash(tx)?;
+
+    while attempts.next(op_name, &e).await {
+        let res = client
+            .client
+            .query::<_, GetTxResponse>(
+                GetTxRequest { hash: hash.clone() },
+                "/cosmos.tx.v1beta1.Service/GetTx",
+            )
+            .await;
+
+        match res {
+            Ok(GetTxResponse {
+                tx_response: Some(res),
+                ..
+            }) => {
+                let res = ChainTxResponse::try_from(res)?;
+                if res.res.code.is_err() {
+                    return Err(ChainError::CosmosSdk { res: res.res }.into());
+                }
+                return Ok(res);
+            }
+            Ok(_) => debug!("tx {hash} not found"),
+            Err(e) => debug!("tx {hash} not found: {e}"),
+        }
+    }
+
+    Err(e)
+}
+
+/// Hash of a signed tx, as reported by the chain
+pub(crate) fn tx_hash(tx: &RawTx) -> Result<String, ChainError> {
+    Ok(format!("{:X}", Sha256::digest(tx.to_bytes()?)))
+}
+
+/// Errors that can wrap a `ChainError` returned by the chain client
+pub(crate) trait AsChainError {
+    fn as_chain_error(&self) -> Option<&ChainError>;
+}
+
+impl AsChainError for ChainError {
+    fn as_chain_error(&self) -> Option<&ChainError> {
+        Some(self)
+    }
+}
+
+impl AsChainError for AccountError {
+    fn as_chain_error(&self) -> Option<&ChainError> {
+        match self {
+            AccountError::ChainError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl AsChainError for TxError {
+    fn as_chain_error(&self) -> Option<&ChainError> {
+        match self {
+            TxError::ChainError(e) => Some(e),
+            TxError::AccountError(e) => e.as_chain_error(),
+            _ => None,
+        }
+    }
+}
+
+impl AsChainError for CosmwasmError {
+    fn as_chain_error(&self) -> Option<&ChainError> {
+        match self {
+            CosmwasmError::ChainError(e) => Some(e),
+            CosmwasmError::TxError(e) => e.as_chain_error(),
+            CosmwasmError::AccountError(e) => e.as_chain_error(),
+            _ => None,
+        }
+    }
+}
+
+impl AsChainError for BankError {
+    fn as_chain_error(&self) -> Option<&ChainError> {
+        match self {
+            BankError::ChainError(e) => Some(e),
+            BankError::TxError(e) => e.as_chain_error(),
+            BankError::AccountError(e) => e.as_chain_error(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RetryPolicy, RetryableError};
+    use cosm_tome::chain::error::ChainError;
+    use cosm_tome::chain::response::{ChainResponse, Code};
+    use std::time::Duration;
+
+    fn sdk_error(code: u32, log: &str) -> ChainError {
+        ChainError::CosmosSdk {
+            res: ChainResponse {
+                code: Code::Err(code),
+                data: None,
+                log: log.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn classify_errors() {
+        assert_eq!(
+            RetryableError::classify(&sdk_error(19, "tx already in mempool")),
+            Some(RetryableError::TxInMempool)
+        );
+        assert_eq!(
+            RetryableError::classify(&sdk_error(
+                32,
+                "account sequence mismatch, expected 2, got 1: incorrect account sequence"
+            )),
+            Some(RetryableError::SequenceMismatch)
+        );
+        assert_eq!(
+            RetryableError::classify(&sdk_error(4, "Timeout expired")),
+            Some(RetryableError::Timeout)
+        );
+        assert_eq!(
+            RetryableError::classify(&sdk_error(14, "status: 503 Service Unavailable")),
+            Some(RetryableError::Unavailable)
+        );
+        assert_eq!(
+            RetryableError::classify(&ChainError::RPC(tendermint_rpc::Error::timeout(
+                Duration::from_secs(5)
+            ))),
+            Some(RetryableError::Timeout)
+        );
+        assert_eq!(RetryableError::classify(&sdk_error(11, "out of gas")), None);
+        assert_eq!(RetryableError::classify(&ChainError::Mnemonic), None);
+    }
+
+    #[test]
+    fn failed_txs_are_not_transient() {
+        for log in [
+            "failed to execute message; message index: 0: insufficient funds: 5031ujunox",
+            "failed to execute message; message index: 0: auction timeout reached",
+            "Generic error: deadline passed: execute wasm contract failed",
+        ] {
+            let e = sdk_error(5, log);
+            assert_eq!(RetryableError::classify(&e), None);
+            assert_eq!(RetryableError::classify_broadcast(&e), None);
+        }
+
+        // gRPC status codes are not trusted for broadcasts, `unauthorized` shares code 4
+        assert_eq!(
+            RetryableError::classify_broadcast(&sdk_error(4, "unauthorized")),
+            None
+        );
+    }
+
+    #[test]
+    fn only_retries_configured_errors() {
+        let policy = RetryPolicy {
+            retry_on: vec![RetryableError::Timeout],
+            ..Default::default()
+        };
+
+        assert!(policy.is_retryable(&sdk_error(4, "request timed out")));
+        assert!(!policy.is_retryable(&sdk_error(19, "tx already in mempool")));
+    }
+
+    #[test]
+    fn default_only_retries_failures_before_broadcast() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy.is_retryable(&sdk_error(14, "status: 503 Service Unavailable")));
+        assert!(policy.is_retryable(&sdk_error(32, "account sequence mismatch")));
+        assert!(!policy.is_retryable(&sdk_error(4, "request timed out")));
+        assert!(!policy.is_retryable(&sdk_error(19, "tx already in mempool")));
+    }
+}
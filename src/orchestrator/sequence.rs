@@ -0,0 +1,191 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tracing::warn;
+
+use cosm_tome::chain::error::ChainError;
+use cosm_tome::chain::msg::Msg;
+use cosm_tome::chain::request::TxOptions;
+use cosm_tome::chain::response::AsyncChainTxResponse;
+use cosm_tome::clients::client::{CosmTome, CosmosClient};
+use cosm_tome::config::cfg::ChainConfig;
+use cosm_tome::modules::auth::model::{Account, Address};
+use cosm_tome::modules::tx::error::TxError;
+use cosm_tome::modules::tx::model::BroadcastMode;
+use cosm_tome::signing_key::key::SigningKey;
+
+use super::retry::{AsChainError, RetryableError};
+
+/// Number of times a tx is re-signed after an `account sequence mismatch` error
+pub const DEFAULT_SEQUENCE_RETRIES: u32 = 3;
+
+/// Tracks the account number and sequence of signing keys locally.
+///
+/// The chain is only queried the first time a key is used and after a failed tx, so one key
+/// can submit several txs that land in the same block, see [CosmOrc::submit_execute()](crate::orchestrator::cosm_orc::CosmOrc::submit_execute).
+/// Txs rejected with `account sequence mismatch` are re-signed with the resynced sequence.
+#[derive(Clone, Debug)]
+pub struct SequenceManager {
+    accounts: BTreeMap<Address, Account>,
+    /// Retries after an `account sequence mismatch` error
+    pub max_retries: u32,
+}
+
+impl Default for SequenceManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SequenceManager {
+    pub fn new() -> Self {
+        Self {
+            accounts: BTreeMap::new(),
+            max_retries: DEFAULT_SEQUENCE_RETRIES,
+        }
+    }
+
+    /// Sets the number of retries after an `account sequence mismatch` error
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Next sequence that `address` will sign with, if it is tracked
+    pub fn sequence(&self, address: &Address) -> Option<u64> {
+        self.accounts.get(address).map(|a| a.sequence)
+    }
+
+    /// Forgets the tracked sequence of `address`, it is queried again on its next tx
+    pub fn resync(&mut self, address: &Address) {
+        self.accounts.remove(address);
+    }
+
+    /// Forgets every tracked sequence
+    pub fn clear(&mut self) {
+        self.accounts.clear();
+    }
+
+    /// Signs `msgs` with the tracked sequence of `sender` and broadcasts them without
+    /// waiting for the tx to be committed.
+    pub(crate) async fn submit<C, M>(
+        &mut self,
+        client: &CosmTome<C>,
+        cfg: &ChainConfig,
+        msgs: Vec<M>,
+        sender: &Address,
+        key: &SigningKey,
+        tx_options: &TxOptions,
+    ) -> Result<AsyncChainTxResponse, TxError>
+    where
+        C: CosmosClient,
+        M: Msg + Serialize,
+    {
+        let mut retries = 0;
+
+        loop {
+            match self
+                .try_submit(client, cfg, msgs.clone(), sender, key, tx_options)
+                .await
+            {
+                Ok(res) => return Ok(res),
+                Err(e) if is_sequence_mismatch(&e) && retries < self.max_retries => {
+                    retries += 1;
+                    warn!("{sender} sequence mismatch, resyncing (retry {retries})");
+                    self.resync(sender);
+                }
+                Err(e) => {
+                    self.resync(sender);
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    async fn try_submit<C, M>(
+        &mut self,
+        client: &CosmTome<C>,
+        cfg: &ChainConfig,
+        msgs: Vec<M>,
+        sender: &Address,
+        key: &SigningKey,
+        tx_options: &TxOptions,
+    ) -> Result<AsyncChainTxResponse, TxError>
+    where
+        C: CosmosClient,
+        M: Msg + Serialize,
+    {
+        let account = match self.accounts.get(sender) {
+            Some(account) => account.clone(),
+            None => client.auth_query_account(sender.clone()).await?.account,
+        };
+
+        let anys = msgs
+            .iter()
+            .map(|m| m.to_any())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ChainError::ProtoEncoding {
+                message: e.to_string(),
+            })?;
+
+        // simulating also runs the ante handler, so it catches stale sequences before signing
+        let sim_fee = client.tx_simulate(anys, &account).await?;
+        let fee = tx_options.fee.clone().unwrap_or(sim_fee);
+
+        let raw = key
+            .sign(
+                msgs,
+                tx_options.timeout_height.unwrap_or_default(),
+                &tx_options.memo,
+                account.clone(),
+                fee,
+                cfg,
+            )
+            .await?;
+
+        let res = client.tx_broadcast(&raw, BroadcastMode::Sync).await?;
+
+        self.accounts.insert(
+            sender.clone(),
+            Account {
+                sequence: account.sequence + 1,
+                ..account
+            },
+        );
+
+        Ok(res)
+    }
+}
+
+/// Returns true if the chain rejected the tx because it was signed with a stale sequence
+pub(crate) fn is_sequence_mismatch(e: &TxError) -> bool {
+    e.as_chain_error()
+        .and_then(RetryableError::classify_broadcast)
+        == Some(RetryableError::SequenceMismatch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_sequence_mismatch;
+    use cosm_tome::chain::error::ChainError;
+    use cosm_tome::chain::response::{ChainResponse, Code};
+    use cosm_tome::modules::tx::error::TxError;
+
+    fn sdk_error(code: u32, log: &str) -> TxError {
+        TxError::ChainError(ChainError::CosmosSdk {
+            res: ChainResponse {
+                code: Code::Err(code),
+                data: None,
+                log: log.to_string(),
+            },
+        })
+    }
+
+    #[test]
+    fn detects_sequence_mismatch() {
+        assert!(is_sequence_mismatch(&sdk_error(
+            32,
+            "account sequence mismatch, expected 5, got 4: incorrect account sequence"
+        )));
+        assert!(!is_sequence_mismatch(&sdk_error(11, "out of gas")));
+    }
+}
@@ -0,0 +1,165 @@
+use serde::Serialize;
+use std::ops::Deref;
+
+use cosm_tome::chain::response::AsyncChainTxResponse;
+use cosm_tome::clients::client::CosmosClient;
+use cosm_tome::modules::auth::model::Address;
+
+use super::cosm_orc::CosmOrc;
+use super::error::{ProcessError, StoreError};
+use super::store::StoreResponse;
+use super::{
+    AccessConfig, Coin, ExecReq, ExecResponse, InstantiateResponse, KeyRef, MigrateResponse,
+    SendReq, SendResponse,
+};
+
+/// Handle that runs `CosmOrc` operations signed by a single key.
+///
+/// Created by [CosmOrc::signer()] and [CosmOrc::with_signer()].
+/// Read only operations like `query()` are available through `Deref`.
+pub struct Signer<'a, C: CosmosClient> {
+    cosm_orc: &'a mut CosmOrc<C>,
+    key: KeyRef<'a>,
+}
+
+impl<'a, C: CosmosClient> Signer<'a, C> {
+    pub(crate) fn new(cosm_orc: &'a mut CosmOrc<C>, key: KeyRef<'a>) -> Self {
+        Self { cosm_orc, key }
+    }
+
+    /// See [CosmOrc::store_contracts()]
+    #[track_caller]
+    pub fn store_contracts(
+        &mut self,
+        wasm_dir: &str,
+        instantiate_perms: Option<AccessConfig>,
+    ) -> Result<Vec<StoreResponse>, StoreError> {
+        self.cosm_orc
+            .store_contracts(wasm_dir, self.key, instantiate_perms)
+    }
+
+    /// See [CosmOrc::instantiate()]
+    #[track_caller]
+    pub fn instantiate<S, T>(
+        &mut self,
+        contract_name: S,
+        op_name: S,
+        msg: &T,
+        admin: Option<Address>,
+        funds: Vec<Coin>,
+    ) -> Result<InstantiateResponse, ProcessError>
+    where
+        S: Into<String>,
+        T: Serialize,
+    {
+        self.cosm_orc
+            .instantiate(contract_name, op_name, msg, self.key, admin, funds)
+    }
+
+    /// See [CosmOrc::execute()]
+    #[track_caller]
+    pub fn execute<S, T>(
+        &mut self,
+        contract_name: S,
+        op_name: S,
+        msg: &T,
+        funds: Vec<Coin>,
+    ) -> Result<ExecResponse, ProcessError>
+    where
+        S: Into<String>,
+        T: Serialize,
+    {
+        self.cosm_orc
+            .execute(contract_name, op_name, msg, self.key, funds)
+    }
+
+    /// See [CosmOrc::execute_batch()]
+    #[track_caller]
+    pub fn execute_batch<S, I>(&mut self, op_name: S, reqs: I) -> Result<ExecResponse, ProcessError>
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = ExecReq>,
+    {
+        self.cosm_orc.execute_batch(op_name, reqs, self.key)
+    }
+
+    /// See [CosmOrc::migrate()]
+    #[track_caller]
+    pub fn migrate<S, T>(
+        &mut self,
+        contract_name: S,
+        new_code_id: u64,
+        op_name: S,
+        msg: &T,
+    ) -> Result<MigrateResponse, ProcessError>
+    where
+        S: Into<String>,
+        T: Serialize,
+    {
+        self.cosm_orc
+            .migrate(contract_name, new_code_id, op_name, msg, self.key)
+    }
+
+    /// See [CosmOrc::send()]
+    #[track_caller]
+    pub fn send<S>(
+        &mut self,
+        to: S,
+        op_name: S,
+        amounts: Vec<Coin>,
+    ) -> Result<SendResponse, ProcessError>
+    where
+        S: Into<String>,
+    {
+        self.cosm_orc.send(to, op_name, amounts, self.key)
+    }
+
+    /// See [CosmOrc::multi_send()]
+    #[track_caller]
+    pub fn multi_send<S, I>(&mut self, op_name: S, reqs: I) -> Result<SendResponse, ProcessError>
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = SendReq>,
+    {
+        self.cosm_orc.multi_send(op_name, reqs, self.key)
+    }
+
+    /// See [CosmOrc::submit_execute()]
+    pub fn submit_execute<S, T>(
+        &mut self,
+        contract_name: S,
+        msg: &T,
+        funds: Vec<Coin>,
+    ) -> Result<AsyncChainTxResponse, ProcessError>
+    where
+        S: Into<String>,
+        T: Serialize,
+    {
+        self.cosm_orc
+            .submit_execute(contract_name, msg, self.key, funds)
+    }
+
+    /// See [CosmOrc::submit_execute_batch()]
+    pub fn submit_execute_batch<I>(&mut self, reqs: I) -> Result<AsyncChainTxResponse, ProcessError>
+    where
+        I: IntoIterator<Item = ExecReq>,
+    {
+        self.cosm_orc.submit_execute_batch(reqs, self.key)
+    }
+
+    /// See [CosmOrc::submit_multi_send()]
+    pub fn submit_multi_send<I>(&mut self, reqs: I) -> Result<AsyncChainTxResponse, ProcessError>
+    where
+        I: IntoIterator<Item = SendReq>,
+    {
+        self.cosm_orc.submit_multi_send(reqs, self.key)
+    }
+}
+
+impl<C: CosmosClient> Deref for Signer<'_, C> {
+    type Target = CosmOrc<C>;
+
+    fn deref(&self) -> &Self::Target {
+        self.cosm_orc
+    }
+}
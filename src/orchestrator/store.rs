@@ -0,0 +1,405 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::env::consts::ARCH;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use cosm_tome::chain::response::ChainTxResponse;
+
+use super::error::StoreError;
+use super::wasm::{WasmInfo, WasmInterface, REQUIRED_EXPORTS, SUPPORTED_INTERFACE_VERSIONS};
+
+/// Maximum wasm size accepted by default, matching the default `MaxWasmSize` of wasmd
+pub const DEFAULT_MAX_WASM_SIZE: u64 = 800 * 1024;
+
+/// Capabilities supported by default, matching the default capabilities of wasmd
+pub const DEFAULT_CAPABILITIES: [&str; 5] = [
+    "iterator",
+    "staking",
+    "stargate",
+    "cosmwasm_1_1",
+    "cosmwasm_1_2",
+];
+
+/// Controls how [CosmOrc::store_contracts()](crate::orchestrator::cosm_orc::CosmOrc::store_contracts)
+/// checks wasm artifacts before uploading them.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StorePolicy {
+    /// Wasm files larger than this many bytes are rejected before broadcasting. `None` disables the check.
+    pub max_wasm_size: Option<u64>,
+    /// Statically checks wasm files for CosmWasm compatibility before broadcasting
+    pub validate_wasm: bool,
+    /// Capabilities supported by the chain, that contracts can require through `requires_*` exports
+    pub capabilities: BTreeSet<String>,
+    /// Selects and names the wasm files to store, defaults to the `artifacts` section of the `Config`
+    pub artifacts: ArtifactFilter,
+}
+
+impl Default for StorePolicy {
+    fn default() -> Self {
+        Self {
+            max_wasm_size: Some(DEFAULT_MAX_WASM_SIZE),
+            validate_wasm: true,
+            capabilities: DEFAULT_CAPABILITIES.iter().map(|c| c.to_string()).collect(),
+            artifacts: ArtifactFilter::default(),
+        }
+    }
+}
+
+impl StorePolicy {
+    /// Checks `wasm` against this policy, returning the first problem found
+    pub fn check(&self, contract: &str, wasm: &[u8], info: &WasmInfo) -> Result<(), StoreError> {
+        if let Some(max_size) = self.max_wasm_size {
+            if info.size > max_size {
+                return Err(StoreError::WasmTooLarge {
+                    contract: contract.to_string(),
+                    size: info.size,
+                    max_size,
+                });
+            }
+        }
+
+        if !self.validate_wasm {
+            return Ok(());
+        }
+
+        let interface = WasmInterface::parse(wasm).map_err(|e| StoreError::InvalidWasm {
+            contract: contract.to_string(),
+            source: e,
+        })?;
+
+        let mut versions = interface.interface_versions.iter();
+        match (versions.next(), versions.next()) {
+            (None, _) => {
+                return Err(StoreError::MissingInterfaceVersion {
+                    contract: contract.to_string(),
+                })
+            }
+            (Some(_), Some(_)) => {
+                return Err(StoreError::MultipleInterfaceVersions {
+                    contract: contract.to_string(),
+                    versions: interface.interface_versions.iter().cloned().collect(),
+                })
+            }
+            (Some(version), None) if !SUPPORTED_INTERFACE_VERSIONS.contains(&version.as_str()) => {
+                return Err(StoreError::UnsupportedInterfaceVersion {
+                    contract: contract.to_string(),
+                    version: version.clone(),
+                })
+            }
+            _ => {}
+        }
+
+        if let Some(export) = REQUIRED_EXPORTS
+            .iter()
+            .find(|e| !interface.exports.contains(**e))
+        {
+            return Err(StoreError::MissingExport {
+                contract: contract.to_string(),
+                export: export.to_string(),
+            });
+        }
+
+        let unsupported: Vec<String> = interface
+            .capabilities
+            .difference(&self.capabilities)
+            .cloned()
+            .collect();
+        if !unsupported.is_empty() {
+            return Err(StoreError::UnsupportedCapabilities {
+                contract: contract.to_string(),
+                capabilities: unsupported,
+            });
+        }
+
+        if let Some(offset) = interface.float_offset {
+            return Err(StoreError::FloatOperation {
+                contract: contract.to_string(),
+                offset,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Selects the wasm files stored by
+/// [CosmOrc::store_contracts()](crate::orchestrator::cosm_orc::CosmOrc::store_contracts)
+/// and the contract names they are registered under.
+///
+/// ```yaml
+/// artifacts:
+///   include: ["cw20_*", "cw4_group*"]
+///   exclude: ["*_test*"]
+///   contract_names:
+///     cw20_base: token
+/// ```
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArtifactFilter {
+    /// Glob patterns matched against wasm file names. When set, only matching files are stored
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Glob patterns matched against wasm file names, matching files are skipped
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Contract names keyed by wasm file stem without the `-{ARCH}` suffix added by optimizoor,
+    /// replacing the name derived from the file name. The full stem is also accepted.
+    #[serde(default)]
+    pub contract_names: BTreeMap<String, String>,
+}
+
+impl ArtifactFilter {
+    /// Returns the contract name and path of every wasm file in `wasm_dir` passing the filters,
+    /// sorted by file name so code ids are assigned in the same order on every run.
+    ///
+    /// Unless mapped in `contract_names`, contracts are named after the file stem,
+    /// without the `-{ARCH}` suffix added by optimizoor.
+    pub fn select(&self, wasm_dir: &Path) -> Result<Vec<(String, PathBuf)>, StoreError> {
+        let include = glob_set(&self.include)?;
+        let exclude = glob_set(&self.exclude)?;
+
+        let mut wasm_paths = vec![];
+        for entry in fs::read_dir(wasm_dir).map_err(StoreError::wasmdir)? {
+            let path = entry?.path();
+            if path.extension() == Some(OsStr::new("wasm")) {
+                wasm_paths.push(path);
+            }
+        }
+        wasm_paths.sort();
+
+        let arch_suffix = format!("-{ARCH}");
+        let mut selected = vec![];
+
+        for path in wasm_paths {
+            let file_name = path
+                .file_name()
+                .and_then(OsStr::to_str)
+                .ok_or(StoreError::InvalidWasmFileName)?;
+
+            if !self.include.is_empty() && !include.is_match(file_name) {
+                continue;
+            }
+            if exclude.is_match(file_name) {
+                continue;
+            }
+
+            let stem = path
+                .file_stem()
+                .and_then(OsStr::to_str)
+                .ok_or(StoreError::InvalidWasmFileName)?;
+
+            // parse out OS architecture if optimizoor was used:
+            let name = stem.strip_suffix(&arch_suffix).unwrap_or(stem);
+
+            let contract = self
+                .contract_names
+                .get(name)
+                .or_else(|| self.contract_names.get(stem))
+                .map_or(name, String::as_str)
+                .to_string();
+
+            selected.push((contract, path));
+        }
+
+        Ok(selected)
+    }
+}
+
+fn glob_set(patterns: &[String]) -> Result<GlobSet, StoreError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).map_err(|e| StoreError::InvalidGlob {
+            pattern: pattern.clone(),
+            source: e,
+        })?);
+    }
+
+    builder.build().map_err(|e| StoreError::InvalidGlob {
+        pattern: patterns.join(","),
+        source: e,
+    })
+}
+
+/// Stored wasm artifact
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StoreResponse {
+    /// Name the contract was registered under in the `ContractMap`
+    pub contract: String,
+    pub code_id: u64,
+    pub wasm: WasmInfo,
+    pub res: ChainTxResponse,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArtifactFilter, StorePolicy};
+    use crate::orchestrator::error::StoreError;
+    use crate::orchestrator::wasm::WasmInfo;
+    use assert_matches::assert_matches;
+    use std::collections::BTreeMap;
+    use std::env::consts::ARCH;
+    use std::fs;
+
+    fn check(policy: &StorePolicy, wat: &str) -> Result<(), StoreError> {
+        let wasm = wat::parse_str(wat).unwrap();
+        let info = WasmInfo::parse(&wasm).unwrap();
+        policy.check("cw_test", &wasm, &info)
+    }
+
+    #[test]
+    fn check_wasm_compatibility() {
+        let policy = StorePolicy::default();
+
+        let valid = r#"(module
+            (func (export "interface_version_8"))
+            (func (export "requires_staking"))
+            (func (export "allocate") (param i32) (result i32) local.get 0)
+            (func (export "deallocate") (param i32))
+        )"#;
+        check(&policy, valid).unwrap();
+
+        let res = check(
+            &policy,
+            r#"(module
+                (func (export "allocate") (param i32) (result i32) local.get 0)
+                (func (export "deallocate") (param i32))
+            )"#,
+        );
+        assert_matches!(res, Err(StoreError::MissingInterfaceVersion { .. }));
+
+        let res = check(
+            &policy,
+            r#"(module
+                (func (export "interface_version_7"))
+                (func (export "allocate") (param i32) (result i32) local.get 0)
+                (func (export "deallocate") (param i32))
+            )"#,
+        );
+        assert_matches!(res, Err(StoreError::UnsupportedInterfaceVersion { version, .. }) if version == "7");
+
+        let res = check(
+            &policy,
+            r#"(module
+                (func (export "interface_version_7"))
+                (func (export "interface_version_8"))
+                (func (export "allocate") (param i32) (result i32) local.get 0)
+                (func (export "deallocate") (param i32))
+            )"#,
+        );
+        assert_matches!(
+            res,
+            Err(StoreError::MultipleInterfaceVersions { versions, .. }) if versions == vec!["7", "8"]
+        );
+
+        let res = check(
+            &policy,
+            r#"(module
+                (func (export "interface_version_8"))
+                (func (export "allocate") (param i32) (result i32) local.get 0)
+            )"#,
+        );
+        assert_matches!(res, Err(StoreError::MissingExport { export, .. }) if export == "deallocate");
+
+        let res = check(
+            &policy,
+            r#"(module
+                (func (export "interface_version_8"))
+                (func (export "requires_neutron"))
+                (func (export "requires_staking"))
+                (func (export "allocate") (param i32) (result i32) local.get 0)
+                (func (export "deallocate") (param i32))
+            )"#,
+        );
+        assert_matches!(
+            res,
+            Err(StoreError::UnsupportedCapabilities { capabilities, .. }) if capabilities == vec!["neutron"]
+        );
+
+        let res = check(
+            &policy,
+            r#"(module
+                (func (export "interface_version_8"))
+                (func (export "allocate") (param i32) (result i32) local.get 0)
+                (func (export "deallocate") (param i32))
+                (func (export "execute") (param f64) (result f64)
+                    local.get 0
+                    local.get 0
+                    f64.add)
+            )"#,
+        );
+        assert_matches!(res, Err(StoreError::FloatOperation { .. }));
+
+        let policy = StorePolicy {
+            validate_wasm: false,
+            ..StorePolicy::default()
+        };
+        check(&policy, "(module)").unwrap();
+    }
+
+    #[test]
+    fn select_artifacts() {
+        let dir = std::env::temp_dir().join(format!("cosm-orc-artifacts-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for file in [
+            "cw4_group.wasm".to_string(),
+            format!("cw20_base-{ARCH}.wasm"),
+            "cw20_test.wasm".to_string(),
+            "cw721_base.wasm".to_string(),
+            "checksums.txt".to_string(),
+        ] {
+            fs::write(dir.join(file), b"").unwrap();
+        }
+
+        let names = |filter: &ArtifactFilter| {
+            filter
+                .select(&dir)
+                .unwrap()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(&ArtifactFilter::default()),
+            vec!["cw20_base", "cw20_test", "cw4_group", "cw721_base"]
+        );
+
+        let filter = ArtifactFilter {
+            include: vec!["cw20_*".to_string(), "cw4_*".to_string()],
+            exclude: vec!["*_test.wasm".to_string()],
+            contract_names: BTreeMap::from([("cw4_group".to_string(), "group".to_string())]),
+        };
+        assert_eq!(names(&filter), vec!["cw20_base", "group"]);
+
+        let filter = ArtifactFilter {
+            contract_names: BTreeMap::from([
+                ("cw20_base".to_string(), "token".to_string()),
+                ("cw4_group".to_string(), "group".to_string()),
+            ]),
+            ..ArtifactFilter::default()
+        };
+        assert_eq!(
+            names(&filter),
+            vec!["token", "cw20_test", "group", "cw721_base"]
+        );
+
+        let filter = ArtifactFilter {
+            contract_names: BTreeMap::from([(format!("cw20_base-{ARCH}"), "token".to_string())]),
+            ..ArtifactFilter::default()
+        };
+        assert_eq!(names(&filter)[0], "token");
+
+        let filter = ArtifactFilter {
+            include: vec!["cw20_[".to_string()],
+            ..ArtifactFilter::default()
+        };
+        assert_matches!(
+            filter.select(&dir),
+            Err(StoreError::InvalidGlob { pattern, .. }) if pattern == "cw20_["
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,331 @@
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tendermint_rpc::event::{Event as RpcEvent, EventData};
+use tendermint_rpc::query::{EventType, Query};
+use tendermint_rpc::{Subscription, SubscriptionClient, Url, WebSocketClient, WebSocketClientUrl};
+use tokio::runtime::Runtime;
+use tokio::time::timeout as _timeout;
+use tracing::warn;
+
+use cosm_tome::chain::response::Event;
+
+use super::error::SubscribeError;
+
+/// Selects which `Tx` events are streamed from a websocket subscription.
+///
+/// An empty filter matches every tx.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    /// Only match txs that emitted a `wasm` event from this contract address
+    pub contract_address: Option<String>,
+    /// Only match txs that emitted `wasm` events with all of these attribute key/value pairs
+    pub wasm_attributes: Vec<(String, String)>,
+}
+
+impl EventFilter {
+    /// Matches txs that emitted a `wasm` event from `address`
+    pub fn contract<S: Into<String>>(address: S) -> Self {
+        Self {
+            contract_address: Some(address.into()),
+            wasm_attributes: vec![],
+        }
+    }
+
+    /// Additionally requires a `wasm` event attribute `key` equal to `value`
+    pub fn attribute<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.wasm_attributes.push((key.into(), value.into()));
+        self
+    }
+
+    pub(crate) fn query(&self) -> Query {
+        let mut query = Query::from(EventType::Tx);
+
+        if let Some(addr) = &self.contract_address {
+            query = query.and_eq("wasm._contract_address", addr.as_str());
+        }
+
+        for (key, value) in &self.wasm_attributes {
+            query = query.and_eq(format!("wasm.{key}"), value.as_str());
+        }
+
+        query
+    }
+}
+
+/// Event received from a tendermint websocket subscription.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChainEvent {
+    /// A new block was committed
+    NewBlock { height: u64 },
+    /// A tx matching the subscription filter was included in a block
+    Tx(TxEvent),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TxEvent {
+    pub tx_hash: String,
+    pub height: u64,
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+    pub log: String,
+    pub events: Vec<Event>,
+}
+
+impl ChainEvent {
+    fn from_rpc(e: RpcEvent) -> Option<ChainEvent> {
+        match e.data {
+            EventData::NewBlock { block, .. } => Some(ChainEvent::NewBlock {
+                height: block?.header.height.value(),
+            }),
+            EventData::Tx { tx_result } => {
+                let tx_hash = e
+                    .events
+                    .as_ref()
+                    .and_then(|events| events.get("tx.hash"))
+                    .and_then(|hashes| hashes.first())
+                    .cloned()
+                    .unwrap_or_default();
+
+                Some(ChainEvent::Tx(TxEvent {
+                    tx_hash,
+                    height: tx_result.height as u64,
+                    gas_wanted: parse_gas(tx_result.result.gas_wanted),
+                    gas_used: parse_gas(tx_result.result.gas_used),
+                    log: tx_result.result.log.unwrap_or_default(),
+                    events: tx_result
+                        .result
+                        .events
+                        .into_iter()
+                        .map(Into::into)
+                        .collect(),
+                }))
+            }
+            EventData::GenericJsonEvent(_) => None,
+        }
+    }
+}
+
+fn parse_gas(gas: Option<String>) -> u64 {
+    gas.and_then(|g| g.parse().ok()).unwrap_or_default()
+}
+
+/// Blocking stream of events from a tendermint websocket subscription.
+///
+/// The websocket connection is closed when the stream is dropped.
+pub struct EventStream {
+    subscription: Subscription,
+    client: Option<WebSocketClient>,
+    // keeps the websocket driver running in between calls to `next_event()`
+    runtime: Runtime,
+}
+
+impl EventStream {
+    pub(crate) fn connect(rpc_endpoint: &str, query: Query) -> Result<EventStream, SubscribeError> {
+        let url = websocket_url(rpc_endpoint)?;
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .map_err(SubscribeError::Runtime)?;
+
+        let (client, driver) = runtime.block_on(WebSocketClient::new(url))?;
+
+        runtime.spawn(async move {
+            if let Err(e) = driver.run().await {
+                warn!("websocket driver stopped: {e}");
+            }
+        });
+
+        let subscription = runtime.block_on(client.subscribe(query))?;
+
+        Ok(EventStream {
+            subscription,
+            client: Some(client),
+            runtime,
+        })
+    }
+
+    /// Blocks the current thread until the next event is received.
+    ///
+    /// Returns `None` once the subscription has been closed.
+    pub fn next_event(&mut self) -> Option<Result<ChainEvent, SubscribeError>> {
+        loop {
+            match self.runtime.block_on(self.subscription.next())? {
+                Ok(e) => {
+                    if let Some(e) = ChainEvent::from_rpc(e) {
+                        return Some(Ok(e));
+                    }
+                }
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+    }
+
+    /// Same as [Self::next_event()], but throws `SubscribeError::Timeout` once `timeout` has elapsed.
+    pub fn next_event_timeout<T: Into<Duration>>(
+        &mut self,
+        timeout: T,
+    ) -> Result<Option<ChainEvent>, SubscribeError> {
+        let timeout = timeout.into();
+
+        loop {
+            let next = self
+                .runtime
+                .block_on(async { _timeout(timeout, self.subscription.next()).await })?;
+
+            match next {
+                Some(Ok(e)) => {
+                    if let Some(e) = ChainEvent::from_rpc(e) {
+                        return Ok(Some(e));
+                    }
+                }
+                Some(Err(e)) => return Err(e.into()),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+impl Iterator for EventStream {
+    type Item = Result<ChainEvent, SubscribeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_event()
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            if let Err(e) = client.close() {
+                warn!("error closing websocket client: {e}");
+            }
+        }
+    }
+}
+
+/// Converts a tendermint rpc endpoint into its websocket endpoint,
+/// eg. `http://localhost:26657/` -> `ws://localhost:26657/websocket`
+fn websocket_url(rpc_endpoint: &str) -> Result<Url, SubscribeError> {
+    let endpoint = rpc_endpoint.trim_end_matches('/');
+
+    let url = if let Some(host) = endpoint.strip_prefix("https://") {
+        format!("wss://{host}")
+    } else if let Some(host) = endpoint.strip_prefix("http://") {
+        format!("ws://{host}")
+    } else {
+        endpoint.to_string()
+    };
+
+    let url = if url.ends_with("/websocket") {
+        url
+    } else {
+        format!("{url}/websocket")
+    };
+
+    url.parse::<Url>()
+        .ok()
+        .filter(|u| WebSocketClientUrl::try_from(u.clone()).is_ok())
+        .ok_or(SubscribeError::InvalidEndpoint { endpoint: url })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{websocket_url, ChainEvent, EventFilter, TxEvent};
+    use crate::orchestrator::error::SubscribeError;
+    use assert_matches::assert_matches;
+    use cosm_tome::chain::response::{Event, Tag};
+    use tendermint_rpc::abci::{tag::Tag as AbciTag, Event as AbciEvent};
+    use tendermint_rpc::event::{Event as RpcEvent, EventData, TxInfo, TxResult};
+    use tendermint_rpc::query::{EventType, Query};
+
+    #[test]
+    fn converts_rpc_endpoint_to_websocket_url() {
+        assert_eq!(
+            websocket_url("http://localhost:26657/")
+                .unwrap()
+                .to_string(),
+            "ws://localhost:26657/websocket"
+        );
+        assert_eq!(
+            websocket_url("https://rpc.uni.junonetwork.io")
+                .unwrap()
+                .to_string(),
+            "wss://rpc.uni.junonetwork.io/websocket"
+        );
+        assert_eq!(
+            websocket_url("ws://localhost:26657/websocket")
+                .unwrap()
+                .to_string(),
+            "ws://localhost:26657/websocket"
+        );
+        assert_matches!(
+            websocket_url("localhost:26657").unwrap_err(),
+            SubscribeError::InvalidEndpoint { .. }
+        );
+    }
+
+    #[test]
+    fn filter_query() {
+        assert_eq!(EventFilter::default().query(), Query::from(EventType::Tx));
+
+        let filter = EventFilter::contract("juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj")
+            .attribute("action", "transfer");
+
+        assert_eq!(
+            filter.query().to_string(),
+            "tm.event = 'Tx' AND wasm._contract_address = 'juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj' AND wasm.action = 'transfer'"
+        );
+    }
+
+    #[test]
+    fn converts_tx_event() {
+        let rpc_event = RpcEvent {
+            query: "tm.event = 'Tx'".to_string(),
+            data: EventData::Tx {
+                tx_result: TxInfo {
+                    height: 1234,
+                    index: Some(0),
+                    tx: vec![],
+                    result: TxResult {
+                        log: Some("log log log".to_string()),
+                        gas_wanted: Some("200".to_string()),
+                        gas_used: Some("100".to_string()),
+                        events: vec![AbciEvent {
+                            type_str: "wasm".to_string(),
+                            attributes: vec![AbciTag {
+                                key: "action".parse().unwrap(),
+                                value: "transfer".parse().unwrap(),
+                            }],
+                        }],
+                    },
+                },
+            },
+            events: Some(
+                [("tx.hash".to_string(), vec!["TX_HASH_0".to_string()])]
+                    .into_iter()
+                    .collect(),
+            ),
+        };
+
+        assert_eq!(
+            ChainEvent::from_rpc(rpc_event),
+            Some(ChainEvent::Tx(TxEvent {
+                tx_hash: "TX_HASH_0".to_string(),
+                height: 1234,
+                gas_wanted: 200,
+                gas_used: 100,
+                log: "log log log".to_string(),
+                events: vec![Event {
+                    type_str: "wasm".to_string(),
+                    attributes: vec![Tag {
+                        key: "action".to_string(),
+                        value: "transfer".to_string(),
+                    }],
+                }],
+            }))
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use std::time::Instant;
+use tracing::span::EnteredSpan;
+use tracing::{debug, Span};
+
+use cosm_tome::chain::response::ChainTxResponse;
+
+/// Creates the `info` span of a tx operation.
+///
+/// `tx_hash`, `gas_wanted` and `gas_used` are recorded by [OpSpan::record_tx()],
+/// `duration_ms` when the [OpSpan] is dropped.
+macro_rules! op_span {
+    ($name:literal, $($fields:tt)*) => {
+        tracing::info_span!(
+            $name,
+            $($fields)*,
+            tx_hash = tracing::field::Empty,
+            gas_wanted = tracing::field::Empty,
+            gas_used = tracing::field::Empty,
+            duration_ms = tracing::field::Empty,
+        )
+    };
+}
+
+pub(crate) use op_span;
+
+/// Entered span of an orchestrator operation, recording its `duration_ms` when dropped
+pub(crate) struct OpSpan {
+    span: EnteredSpan,
+    start: Instant,
+}
+
+impl OpSpan {
+    pub(crate) fn enter(span: Span) -> Self {
+        Self {
+            span: span.entered(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Records the committed tx on the span
+    pub(crate) fn record_tx(&self, res: &ChainTxResponse) {
+        self.span.record("tx_hash", res.tx_hash.as_str());
+        self.span.record("gas_wanted", res.gas_wanted);
+        self.span.record("gas_used", res.gas_used);
+
+        debug!(
+            tx_hash = %res.tx_hash,
+            height = res.height,
+            gas_wanted = res.gas_wanted,
+            gas_used = res.gas_used,
+            "tx committed"
+        );
+    }
+}
+
+impl Drop for OpSpan {
+    fn drop(&mut self) {
+        self.span
+            .record("duration_ms", self.start.elapsed().as_millis() as u64);
+    }
+}
@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use cosm_tome::chain::coin::Coin;
+use cosm_tome::chain::response::{ChainTxResponse, Event};
+use cosm_tome::modules::cosmwasm::model::AccessConfig;
+
+use super::error::TranscriptError;
+
+/// Operation recorded in a transcript.
+///
+/// Contracts are referenced by name, so replaying a transcript resolves them through the
+/// `ContractMap` of the replaying `CosmOrc`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptOp {
+    Store {
+        contract: String,
+        wasm_path: PathBuf,
+        instantiate_perms: Option<AccessConfig>,
+    },
+    Instantiate {
+        contract: String,
+        op_name: String,
+        msg: serde_json::Value,
+        admin: Option<String>,
+        funds: Vec<Coin>,
+    },
+    Execute {
+        contract: String,
+        op_name: String,
+        msg: serde_json::Value,
+        funds: Vec<Coin>,
+    },
+    ExecuteBatch {
+        op_name: String,
+        reqs: Vec<TranscriptExec>,
+    },
+    Migrate {
+        contract: String,
+        op_name: String,
+        /// Name of the stored contract that `new_code_id` belonged to, remapped on replay
+        new_contract: Option<String>,
+        new_code_id: u64,
+        msg: serde_json::Value,
+    },
+    Query {
+        contract: String,
+        msg: serde_json::Value,
+    },
+    Send {
+        to: String,
+        op_name: String,
+        amounts: Vec<Coin>,
+    },
+    MultiSend {
+        op_name: String,
+        reqs: Vec<TranscriptSend>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TranscriptExec {
+    pub contract: String,
+    pub msg: serde_json::Value,
+    pub funds: Vec<Coin>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TranscriptSend {
+    /// Recipient address, or the name of a deployed smart contract
+    pub to: String,
+    pub amounts: Vec<Coin>,
+}
+
+/// Committed tx of a recorded operation
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TxRecord {
+    pub tx_hash: String,
+    pub height: u64,
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+    /// Fee paid by the tx
+    #[serde(default)]
+    pub fee: Vec<Coin>,
+    pub events: Vec<Event>,
+}
+
+impl TxRecord {
+    pub(crate) fn new(res: &ChainTxResponse, fee: Vec<Coin>) -> Self {
+        Self {
+            tx_hash: res.tx_hash.clone(),
+            height: res.height,
+            gas_wanted: res.gas_wanted,
+            gas_used: res.gas_used,
+            fee,
+            events: res.events.clone(),
+        }
+    }
+}
+
+/// One line of a JSONL transcript
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TranscriptEntry {
+    pub op: TranscriptOp,
+    /// Address of the key that signed the tx, None for queries
+    pub signer: Option<String>,
+    /// Name of the signing key, used to pick the same key from `Config::keys` on replay
+    pub key: Option<String>,
+    /// Committed tx, None for queries and failed operations
+    pub result: Option<TxRecord>,
+    /// Error returned by the operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl TranscriptEntry {
+    pub(crate) fn new(
+        op: TranscriptOp,
+        signer: Option<String>,
+        key: Option<String>,
+        result: Result<Option<TxRecord>, String>,
+    ) -> Self {
+        let (result, error) = match result {
+            Ok(res) => (res, None),
+            Err(e) => (None, Some(e)),
+        };
+
+        Self {
+            op,
+            signer,
+            key,
+            result,
+            error,
+        }
+    }
+}
+
+/// Appends `entry` to the transcript at `path`
+pub(crate) fn append<P: AsRef<Path>>(path: P, entry: &TranscriptEntry) -> Result<(), io::Error> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    let mut line = serde_json::to_vec(entry)?;
+    line.push(b'\n');
+
+    file.write_all(&line)
+}
+
+/// Reads every entry of the transcript at `path`
+pub fn read<P: AsRef<Path>>(path: P) -> Result<Vec<TranscriptEntry>, TranscriptError> {
+    fs::read_to_string(path)?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|source| TranscriptError::InvalidEntry {
+                line: i + 1,
+                source,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{append, read, TranscriptEntry, TranscriptOp, TxRecord};
+    use crate::orchestrator::error::TranscriptError;
+    use crate::orchestrator::Coin;
+    use assert_matches::assert_matches;
+    use cosm_tome::chain::response::{ChainResponse, ChainTxResponse, Code};
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn append_and_read() {
+        let path =
+            std::env::temp_dir().join(format!("cosm-orc-transcript-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let execute = TranscriptEntry::new(
+            TranscriptOp::Execute {
+                contract: "cw20_base".to_string(),
+                op_name: "transfer".to_string(),
+                msg: json!({"transfer": {"amount": "10"}}),
+                funds: vec![Coin {
+                    denom: "ujunox".parse().unwrap(),
+                    amount: 5,
+                }],
+            },
+            Some("juno10j9gpw9t4jsz47qgnkvl5n3zlm2fz72k67rxsg".to_string()),
+            Some("validator".to_string()),
+            Ok(Some(TxRecord::new(
+                &ChainTxResponse {
+                    res: ChainResponse {
+                        code: Code::Ok,
+                        data: None,
+                        log: "".to_string(),
+                    },
+                    events: vec![],
+                    gas_wanted: 101,
+                    gas_used: 100,
+                    tx_hash: "TX_HASH_0".to_string(),
+                    height: 1234,
+                },
+                vec![],
+            ))),
+        );
+        let query = TranscriptEntry::new(
+            TranscriptOp::Query {
+                contract: "cw20_base".to_string(),
+                msg: json!({"balance": {}}),
+            },
+            None,
+            None,
+            Err("contract not deployed".to_string()),
+        );
+
+        append(&path, &execute).unwrap();
+        append(&path, &query).unwrap();
+
+        let entries = read(&path).unwrap();
+        assert_eq!(entries, vec![execute, query]);
+        assert_eq!(entries[0].result.as_ref().unwrap().tx_hash, "TX_HASH_0");
+
+        fs::write(&path, "{\"op\": {\"execute\": {}}}\n").unwrap();
+        assert_matches!(
+            read(&path),
+            Err(TranscriptError::InvalidEntry { line: 1, .. })
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use wasmparser::{
+    BinaryReaderError, ExternalKind, Parser, Payload, ValidPayload, Validator, WasmFeatures,
+};
+
+/// Prefix of the export a contract uses to declare the CosmWasm interface version it was built for
+pub const INTERFACE_VERSION_PREFIX: &str = "interface_version_";
+
+/// Interface versions supported by wasmd, a contract must declare exactly one of them
+pub const SUPPORTED_INTERFACE_VERSIONS: [&str; 1] = ["8"];
+
+/// Prefix of the exports a contract uses to declare the chain capabilities it requires
+pub const REQUIRES_PREFIX: &str = "requires_";
+
+/// Function exports every CosmWasm contract needs so the host can manage its memory
+pub const REQUIRED_EXPORTS: [&str; 2] = ["allocate", "deallocate"];
+
+/// Size, checksum and interface of a wasm artifact
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WasmInfo {
+    /// Size of the wasm file in bytes
+    pub size: u64,
+    /// Hex encoded sha256 checksum, matching the `data_hash` reported by the chain
+    pub checksum: String,
+    /// Number of exported functions
+    pub exported_functions: u32,
+    /// Number of imported functions, tables, memories and globals
+    pub imports: u32,
+}
+
+impl WasmInfo {
+    pub fn parse(wasm: &[u8]) -> Result<Self, BinaryReaderError> {
+        let mut exported_functions = 0;
+        let mut imports = 0;
+
+        for payload in Parser::new(0).parse_all(wasm) {
+            match payload? {
+                Payload::ImportSection(reader) => imports += reader.count(),
+                Payload::ExportSection(reader) => {
+                    for export in reader {
+                        if export?.kind == ExternalKind::Func {
+                            exported_functions += 1;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            size: wasm.len() as u64,
+            checksum: format!("{:x}", Sha256::digest(wasm)),
+            exported_functions,
+            imports,
+        })
+    }
+}
+
+/// CosmWasm interface of a wasm artifact, used to check it before uploading
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WasmInterface {
+    /// Names of all exports
+    pub exports: BTreeSet<String>,
+    /// Versions declared by `interface_version_*` exports
+    pub interface_versions: BTreeSet<String>,
+    /// Capabilities declared by `requires_*` exports
+    pub capabilities: BTreeSet<String>,
+    /// Offset of the first float type or operation
+    pub float_offset: Option<usize>,
+}
+
+impl WasmInterface {
+    /// Parses and validates `wasm`, floats are reported in `float_offset` instead of failing
+    /// validation.
+    pub fn parse(wasm: &[u8]) -> Result<Self, BinaryReaderError> {
+        let mut interface = Self::default();
+        let mut validator = Validator::new_with_features(WasmFeatures {
+            floats: false,
+            ..Default::default()
+        });
+
+        for payload in Parser::new(0).parse_all(wasm) {
+            let payload = payload?;
+
+            // the validator can't go on after rejecting a float, but the exports are still read
+            if interface.float_offset.is_none() {
+                let res = match validator.payload(&payload) {
+                    Ok(ValidPayload::Func(func, body)) => {
+                        func.into_validator(Default::default()).validate(&body)
+                    }
+                    Ok(_) => Ok(()),
+                    Err(e) => Err(e),
+                };
+
+                match res {
+                    Err(e) if e.message().starts_with(FLOAT_ERROR_PREFIX) => {
+                        interface.float_offset = Some(e.offset());
+                    }
+                    res => res?,
+                }
+            }
+
+            if let Payload::ExportSection(reader) = payload {
+                for export in reader {
+                    let name = export?.name;
+                    if let Some(version) = name.strip_prefix(INTERFACE_VERSION_PREFIX) {
+                        interface.interface_versions.insert(version.to_string());
+                    }
+                    if let Some(capability) = name.strip_prefix(REQUIRES_PREFIX) {
+                        interface.capabilities.insert(capability.to_string());
+                    }
+                    interface.exports.insert(name.to_string());
+                }
+            }
+        }
+
+        Ok(interface)
+    }
+}
+
+/// Prefix of the validation errors raised for float types and operations, which the chain
+/// rejects as non deterministic
+const FLOAT_ERROR_PREFIX: &str = "floating-point";
+
+#[cfg(test)]
+mod tests {
+    use super::{WasmInfo, WasmInterface};
+
+    #[test]
+    fn parse_wasm_info() {
+        let wasm = wat::parse_str(
+            r#"(module
+                (import "env" "db_read" (func (param i32) (result i32)))
+                (import "env" "abort" (func (param i32)))
+                (memory (export "memory") 1)
+                (func (export "allocate") (param i32) (result i32) local.get 0)
+                (func (export "deallocate") (param i32))
+            )"#,
+        )
+        .unwrap();
+
+        let info = WasmInfo::parse(&wasm).unwrap();
+        assert_eq!(info.size, wasm.len() as u64);
+        assert_eq!(info.exported_functions, 2);
+        assert_eq!(info.imports, 2);
+        assert_eq!(info.checksum.len(), 64);
+
+        assert!(WasmInfo::parse(b"not wasm").is_err());
+    }
+
+    #[test]
+    fn parse_wasm_interface() {
+        let wasm = wat::parse_str(
+            r#"(module
+                (import "env" "abort" (func (param i32)))
+                (func (export "interface_version_8"))
+                (func (export "requires_staking"))
+                (func (export "requires_stargate"))
+                (func (export "allocate") (param i32) (result i32) local.get 0)
+                (func (export "deallocate") (param i32))
+                (func (export "instantiate") (result i32)
+                    f32.const 1.5
+                    i32.trunc_f32_s)
+            )"#,
+        )
+        .unwrap();
+
+        let interface = WasmInterface::parse(&wasm).unwrap();
+        assert!(interface.exports.contains("allocate"));
+        assert_eq!(
+            interface.interface_versions.into_iter().collect::<Vec<_>>(),
+            vec!["8"]
+        );
+        assert_eq!(
+            interface.capabilities.into_iter().collect::<Vec<_>>(),
+            vec!["staking", "stargate"]
+        );
+        assert!(interface.float_offset.is_some());
+
+        let wasm = wat::parse_str(
+            r#"(module
+                (func (export "interface_version_8"))
+                (func (export "instantiate") (result i32) i32.const 1)
+            )"#,
+        )
+        .unwrap();
+        assert_eq!(WasmInterface::parse(&wasm).unwrap().float_offset, None);
+    }
+}
@@ -0,0 +1,314 @@
+//! Assertion helpers for integration tests.
+//!
+//! On failure, the helpers panic with the tx hash, the raw chain log and the
+//! location of the calling test.
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! # use cosm_orc::{
+//! #    config::cfg::Config,
+//! #    orchestrator::cosm_orc::CosmOrc,
+//! # };
+//! # use cosm_orc::orchestrator::{SigningKey, Key};
+//! # use cosm_orc::{assert_attribute, assert_contract_error, assert_tx_success};
+//! # use cw20_base::msg::ExecuteMsg;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # let mut cosm_orc = CosmOrc::new(Config::from_yaml("./example-configs/juno_local.yaml")?, false)?;
+//! # let key = SigningKey {
+//! #     name: "validator".to_string(),
+//! #     key: Key::Mnemonic("word1 word2 ...".to_string()),
+//! #     derivation_path: "m/44'/118'/0'/0/0".to_string(),
+//! # };
+//! let res = cosm_orc.execute(
+//!     "cw20_base",
+//!     "transfer",
+//!     &ExecuteMsg::Transfer {
+//!         recipient: "juno1...".to_string(),
+//!         amount: 100u128.into(),
+//!     },
+//!     &key,
+//!     vec![],
+//! );
+//!
+//! let res = assert_tx_success!(res);
+//! assert_attribute!(res, "wasm", "action", "transfer");
+//!
+//! let res = cosm_orc.execute(
+//!     "cw20_base",
+//!     "burn_too_much",
+//!     &ExecuteMsg::Burn {
+//!         amount: u128::MAX.into(),
+//!     },
+//!     &key,
+//!     vec![],
+//! );
+//!
+//! assert_contract_error!(res, "Overflow");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::panic::Location;
+
+use cosm_tome::chain::response::ChainTxResponse;
+
+use crate::orchestrator::error::ProcessError;
+use crate::orchestrator::events::TxEvents;
+
+/// Asserts that the tx was included in a block without error, returning the response.
+#[track_caller]
+pub fn assert_tx_success<R: AsRef<ChainTxResponse>>(res: &Result<R, ProcessError>) -> &R {
+    match res {
+        Ok(r) => {
+            if r.as_ref().res.code.is_err() {
+                fail_tx("expected tx to succeed", r.as_ref());
+            }
+            r
+        }
+        Err(e) => fail_err("expected tx to succeed", e),
+    }
+}
+
+/// Asserts that the tx failed with an error log containing `expected`.
+#[track_caller]
+pub fn assert_contract_error<R: AsRef<ChainTxResponse>>(
+    res: &Result<R, ProcessError>,
+    expected: &str,
+) {
+    match res {
+        Ok(r) => fail_tx(
+            &format!("expected tx to fail with error containing {expected:?}"),
+            r.as_ref(),
+        ),
+        Err(e) => {
+            if !error_log(e).contains(expected) {
+                fail_err(&format!("expected error containing {expected:?}"), e);
+            }
+        }
+    }
+}
+
+/// Asserts that the tx emitted at least one `event_type` event.
+#[track_caller]
+pub fn assert_event<R: TxEvents + AsRef<ChainTxResponse>>(res: &R, event_type: &str) {
+    if res.find_events(event_type).is_empty() {
+        fail_tx(&format!("expected {event_type:?} event"), res.as_ref());
+    }
+}
+
+/// Asserts that an `event_type` event has a `key` attribute equal to `expected`.
+#[track_caller]
+pub fn assert_attribute<R: TxEvents + AsRef<ChainTxResponse>>(
+    res: &R,
+    event_type: &str,
+    key: &str,
+    expected: &str,
+) {
+    let values = res.attributes(event_type, key);
+    if !values.contains(&expected) {
+        fail_tx(
+            &format!("expected {event_type}.{key} == {expected:?}, found {values:?}"),
+            res.as_ref(),
+        );
+    }
+}
+
+/// Asserts that a `wasm` event emitted by `contract_addr` has a `key` attribute equal to `expected`.
+#[track_caller]
+pub fn assert_wasm_attribute<R: TxEvents + AsRef<ChainTxResponse>>(
+    res: &R,
+    contract_addr: &str,
+    key: &str,
+    expected: &str,
+) {
+    let values = res
+        .wasm_events(contract_addr)
+        .into_iter()
+        .flat_map(|e| e.attributes.iter())
+        .filter(|a| a.key == key)
+        .map(|a| a.value.as_str())
+        .collect::<Vec<_>>();
+
+    if !values.contains(&expected) {
+        fail_tx(
+            &format!("expected {contract_addr} wasm.{key} == {expected:?}, found {values:?}"),
+            res.as_ref(),
+        );
+    }
+}
+
+/// Asserts that the tx used less than `max_gas` gas.
+#[track_caller]
+pub fn assert_gas_used_below<R: AsRef<ChainTxResponse>>(res: &R, max_gas: u64) {
+    let gas_used = res.as_ref().gas_used;
+    if gas_used >= max_gas {
+        fail_tx(
+            &format!("expected gas used below {max_gas}, used {gas_used}"),
+            res.as_ref(),
+        );
+    }
+}
+
+fn error_log(e: &ProcessError) -> String {
+    e.chain_response()
+        .map(|res| res.log.clone())
+        .unwrap_or_else(|| e.to_string())
+}
+
+#[track_caller]
+fn fail_tx(msg: &str, res: &ChainTxResponse) -> ! {
+    panic!(
+        "{msg}\n  tx_hash: {}\n  raw_log: {}\n  at: {}",
+        res.tx_hash,
+        res.res.log,
+        Location::caller()
+    );
+}
+
+#[track_caller]
+fn fail_err(msg: &str, e: &ProcessError) -> ! {
+    panic!(
+        "{msg}\n  error: {e}\n  raw_log: {}\n  at: {}",
+        error_log(e),
+        Location::caller()
+    );
+}
+
+/// Asserts that a `Result<_, ProcessError>` tx succeeded, evaluating to the response.
+/// See [testing::assert_tx_success()](crate::testing::assert_tx_success)
+#[macro_export]
+macro_rules! assert_tx_success {
+    ($res:expr) => {
+        $crate::testing::assert_tx_success(&$res)
+    };
+}
+
+/// Asserts that a `Result<_, ProcessError>` tx failed with an error containing `expected`.
+/// See [testing::assert_contract_error()](crate::testing::assert_contract_error)
+#[macro_export]
+macro_rules! assert_contract_error {
+    ($res:expr, $expected:expr) => {
+        $crate::testing::assert_contract_error(&$res, $expected)
+    };
+}
+
+/// Asserts that a tx response contains an event of type `event_type`.
+/// See [testing::assert_event()](crate::testing::assert_event)
+#[macro_export]
+macro_rules! assert_event {
+    ($res:expr, $event_type:expr) => {
+        $crate::testing::assert_event(&$res, $event_type)
+    };
+}
+
+/// Asserts that a tx response has an `event_type.key` attribute equal to `expected`.
+/// See [testing::assert_attribute()](crate::testing::assert_attribute)
+#[macro_export]
+macro_rules! assert_attribute {
+    ($res:expr, $event_type:expr, $key:expr, $expected:expr) => {
+        $crate::testing::assert_attribute(&$res, $event_type, $key, $expected)
+    };
+}
+
+/// Asserts that a tx response used less than `max_gas` gas.
+/// See [testing::assert_gas_used_below()](crate::testing::assert_gas_used_below)
+#[macro_export]
+macro_rules! assert_gas_used_below {
+    ($res:expr, $max_gas:expr) => {
+        $crate::testing::assert_gas_used_below(&$res, $max_gas)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::orchestrator::error::ProcessError;
+    use crate::orchestrator::ExecResponse;
+    use cosm_tome::chain::error::ChainError;
+    use cosm_tome::chain::response::{ChainResponse, ChainTxResponse, Code, Event, Tag};
+    use cosm_tome::modules::cosmwasm::error::CosmwasmError;
+    use cosm_tome::modules::tx::error::TxError;
+
+    fn exec_res() -> Result<ExecResponse, ProcessError> {
+        Ok(ExecResponse {
+            res: ChainTxResponse {
+                res: ChainResponse {
+                    code: Code::Ok,
+                    data: None,
+                    log: "log log log".to_string(),
+                },
+                events: vec![Event {
+                    type_str: "wasm".to_string(),
+                    attributes: vec![
+                        Tag {
+                            key: "_contract_address".to_string(),
+                            value: "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj".to_string(),
+                        },
+                        Tag {
+                            key: "action".to_string(),
+                            value: "transfer".to_string(),
+                        },
+                    ],
+                }],
+                gas_wanted: 200,
+                gas_used: 100,
+                tx_hash: "TX_HASH_0".to_string(),
+                height: 1234,
+            },
+        })
+    }
+
+    fn exec_err() -> Result<ExecResponse, ProcessError> {
+        Err(ProcessError::CosmwasmError(CosmwasmError::TxError(
+            TxError::ChainError(ChainError::CosmosSdk {
+                res: ChainResponse {
+                    code: Code::Err(5),
+                    data: None,
+                    log: "failed to execute message; message index: 0: Unauthorized: execute wasm contract failed".to_string(),
+                },
+            }),
+        )))
+    }
+
+    #[test]
+    fn passing_assertions() {
+        let res = exec_res();
+
+        let res = assert_tx_success!(res);
+        assert_event!(res, "wasm");
+        assert_attribute!(res, "wasm", "action", "transfer");
+        assert_gas_used_below!(res, 101);
+
+        super::assert_wasm_attribute(
+            res,
+            "juno1ft5zfffrgtm2u72cup9e2ecfxjwz8ztc929cgj",
+            "action",
+            "transfer",
+        );
+
+        assert_contract_error!(exec_err(), "Unauthorized");
+    }
+
+    #[test]
+    #[should_panic(expected = "raw_log: failed to execute message; message index: 0: Unauthorized")]
+    fn tx_success_fails_with_raw_log() {
+        assert_tx_success!(exec_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "tx_hash: TX_HASH_0")]
+    fn contract_error_fails_on_success() {
+        assert_contract_error!(exec_res(), "Unauthorized");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected wasm.action == \"burn\", found [\"transfer\"]")]
+    fn attribute_fails_with_found_values() {
+        assert_attribute!(exec_res().unwrap(), "wasm", "action", "burn");
+    }
+
+    #[test]
+    #[should_panic(expected = "src/testing.rs")]
+    fn gas_used_fails_with_caller_location() {
+        assert_gas_used_below!(exec_res().unwrap(), 100);
+    }
+}